@@ -0,0 +1,169 @@
+//! Minimal bidirectional-text reordering for `renderer::rasterize_text`.
+//!
+//! This is not a full UAX #9 implementation — no explicit embedding or
+//! override codes, no isolates, no paired-bracket resolution. It covers the
+//! case this overlay actually needs to render correctly: a short label or
+//! toast string made of one or more runs of strong-LTR or strong-RTL script,
+//! with embedded weak characters (digits) and neutrals (spaces, punctuation)
+//! taking on whichever run they sit in. That's enough to keep Arabic/Hebrew
+//! game names and menu labels from coming out character-reversed.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Requested paragraph direction. `Auto` derives the base direction from the
+/// first strong (script) character in the string, defaulting to LTR if none
+/// is found — the same heuristic used for `dir="auto"` in HTML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    /// Carries `true` for RTL script (Hebrew/Arabic-family blocks).
+    Strong(bool),
+    /// Digits: direction-less, take on the surrounding run's direction.
+    Weak,
+    /// Everything else (whitespace, punctuation): same treatment as `Weak`.
+    Neutral,
+}
+
+fn classify(ch: char) -> CharClass {
+    let cp = ch as u32;
+    let is_rtl_script = matches!(cp,
+        0x0590..=0x05FF   // Hebrew
+        | 0x0600..=0x06FF // Arabic
+        | 0x0700..=0x074F // Syriac
+        | 0x0750..=0x077F // Arabic Supplement
+        | 0x08A0..=0x08FF // Arabic Extended-A
+        | 0xFB1D..=0xFB4F // Hebrew Presentation Forms
+        | 0xFB50..=0xFDFF // Arabic Presentation Forms-A
+        | 0xFE70..=0xFEFF // Arabic Presentation Forms-B
+    );
+    if is_rtl_script {
+        CharClass::Strong(true)
+    } else if ch.is_alphabetic() {
+        CharClass::Strong(false)
+    } else if ch.is_numeric() {
+        CharClass::Weak
+    } else {
+        CharClass::Neutral
+    }
+}
+
+/// Resolve `Auto` against `text`'s first strong character.
+fn base_direction(text: &str, requested: Direction) -> bool {
+    match requested {
+        Direction::Ltr => false,
+        Direction::Rtl => true,
+        Direction::Auto => text
+            .chars()
+            .find_map(|c| match classify(c) {
+                CharClass::Strong(rtl) => Some(rtl),
+                _ => None,
+            })
+            .unwrap_or(false),
+    }
+}
+
+/// Split `text` into direction runs and return them in *visual* order — the
+/// order a caller should lay them out left-to-right — each paired with
+/// whether that run itself reads right-to-left. Weak/neutral characters
+/// join whichever strong run precedes them (or the paragraph base
+/// direction, for a leading weak/neutral run).
+///
+/// Within an RTL run, grapheme clusters (not raw `char`s) are reversed so a
+/// left-to-right glyph cursor produces the correct visual order without
+/// relocating a combining mark (e.g. Hebrew niqqud) ahead of its base
+/// letter; the run ordering itself is reversed too when the paragraph's
+/// base direction is RTL.
+pub fn visual_runs(text: &str, direction: Direction) -> Vec<(String, bool)> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    let base_rtl = base_direction(text, direction);
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut levels = vec![base_rtl; chars.len()];
+    let mut last_strong = None;
+    for (i, &ch) in chars.iter().enumerate() {
+        match classify(ch) {
+            CharClass::Strong(rtl) => {
+                last_strong = Some(rtl);
+                levels[i] = rtl;
+            }
+            CharClass::Weak | CharClass::Neutral => {
+                levels[i] = last_strong.unwrap_or(base_rtl);
+            }
+        }
+    }
+
+    let mut logical_runs: Vec<(String, bool)> = Vec::new();
+    let mut run = String::new();
+    let mut run_rtl = levels[0];
+    for (i, &ch) in chars.iter().enumerate() {
+        if levels[i] != run_rtl {
+            logical_runs.push((std::mem::take(&mut run), run_rtl));
+            run_rtl = levels[i];
+        }
+        run.push(ch);
+    }
+    logical_runs.push((run, run_rtl));
+
+    let mut visual: Vec<(String, bool)> = logical_runs
+        .into_iter()
+        .map(|(s, rtl)| {
+            if rtl {
+                (s.graphemes(true).rev().collect(), rtl)
+            } else {
+                (s, rtl)
+            }
+        })
+        .collect();
+    if base_rtl {
+        visual.reverse();
+    }
+    visual
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pure_ltr_is_a_single_unreversed_run() {
+        let runs = visual_runs("Resume Game", Direction::Auto);
+        assert_eq!(runs, vec![("Resume Game".to_string(), false)]);
+    }
+
+    #[test]
+    fn pure_rtl_run_is_reversed() {
+        // "שלום" (Hebrew for "hello") — should come back char-reversed.
+        let runs = visual_runs("שלום", Direction::Auto);
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0].1);
+        assert_eq!(runs[0].0, "שלום".chars().rev().collect::<String>());
+    }
+
+    #[test]
+    fn explicit_ltr_overrides_script_detection() {
+        let runs = visual_runs("שלום", Direction::Ltr);
+        assert_eq!(runs, vec![("שלום".to_string(), false)]);
+    }
+
+    #[test]
+    fn digits_join_the_preceding_rtl_run() {
+        let runs = visual_runs("שלום42", Direction::Auto);
+        // Digits are weak and attach to the preceding strong run's
+        // direction, so the whole string stays one RTL run.
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0].1);
+    }
+
+    #[test]
+    fn empty_string_has_no_runs() {
+        assert!(visual_runs("", Direction::Auto).is_empty());
+    }
+}
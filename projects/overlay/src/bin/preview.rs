@@ -1,25 +1,35 @@
 //! Local preview tool — renders overlay widgets to PNG files + atlas.
 //! No X11 needed; runs on macOS/Linux/Windows.
 //!
-//! Usage: cargo run --bin preview [-- --theme-root PATH]
+//! Usage: cargo run --bin preview [-- --theme-root PATH] [--animate]
 //!
 //! Outputs:
 //!   preview-output/toast-*.png      — achievement toast variants
 //!   preview-output/menu-*.png       — menu panel at each cursor position
 //!   preview-output/combined-*.png   — full frame with all widgets composited
 //!   preview-output/atlas.png        — single tiled overview
+//!   preview-output/anim-*.png       — (--animate only) animated PNGs driven
+//!                                      by the real Popup/Menu tick timing,
+//!                                      for eyeballing slide-in/hold/fade-out
 
 use std::path::PathBuf;
+use std::time::Duration;
 
 use superkonna_overlay::config::OverlayConfig;
 use superkonna_overlay::menu::Menu;
-use superkonna_overlay::popup::Popup;
+use superkonna_overlay::popup::{Popup, PopupQueue};
 use superkonna_overlay::renderer::{FrameState, Renderer};
 use superkonna_overlay::theme::Theme;
 
 const SCREEN_W: u32 = 1280;
 const SCREEN_H: u32 = 720;
 
+// Animation timeline: 96 frames at ~16ms is ~1.5s, enough to cover a
+// shortened demo toast's slide-in (300ms) + hold + fade-out (500ms).
+const ANIMATE_FRAMES: u32 = 96;
+const ANIMATE_DT_MS: u64 = 16;
+const ANIMATE_DEMO_HOLD_MS: u64 = 700;
+
 fn main() {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
 
@@ -32,6 +42,8 @@ fn main() {
             manifest.parent().unwrap().parent().unwrap().to_path_buf()
         });
 
+    let animate = std::env::args().any(|a| a == "--animate");
+
     println!("theme root: {}", theme_root.display());
 
     let theme = Theme::load(&theme_root).expect("failed to load theme");
@@ -57,10 +69,12 @@ fn main() {
     for (i, (title, desc)) in toasts.iter().enumerate() {
         let mut popup = Popup::new(title.to_string(), desc.to_string());
         popup.force_hold();
+        let queue = PopupQueue::with_visible(vec![popup]);
         let state = FrameState {
-            popup: Some(&popup),
+            popup: &queue,
             menu: None,
             menu_config: &config.menu,
+            text_fit: &config.text_fit,
             game_name: None,
         };
         let argb = rend.render_frame(&state, SCREEN_W, SCREEN_H);
@@ -76,10 +90,12 @@ fn main() {
         let mut popup = Popup::new("Badge Test".to_string(), "With actual badge image".to_string())
             .with_badge(badge_png);
         popup.force_hold();
+        let queue = PopupQueue::with_visible(vec![popup]);
         let state = FrameState {
-            popup: Some(&popup),
+            popup: &queue,
             menu: None,
             menu_config: &config.menu,
+            text_fit: &config.text_fit,
             game_name: None,
         };
         let argb = rend.render_frame(&state, SCREEN_W, SCREEN_H);
@@ -94,13 +110,16 @@ fn main() {
     let items = config.menu.items.clone();
     let menu_start = all_frames.len();
 
+    let no_toasts = PopupQueue::new();
+
     for cursor in 0..items.len() {
         let mut menu = Menu::new(items.clone());
         force_menu_open(&mut menu, cursor);
         let state = FrameState {
-            popup: None,
+            popup: &no_toasts,
             menu: Some(&menu),
             menu_config: &config.menu,
+            text_fit: &config.text_fit,
             game_name: Some("Super Mario World"),
         };
         let argb = rend.render_frame(&state, SCREEN_W, SCREEN_H);
@@ -118,9 +137,10 @@ fn main() {
         menu.select();
         if menu.is_visible() {
             let state = FrameState {
-                popup: None,
+                popup: &no_toasts,
                 menu: Some(&menu),
                 menu_config: &config.menu,
+                text_fit: &config.text_fit,
                 game_name: Some("Super Mario World"),
             };
             let argb = rend.render_frame(&state, SCREEN_W, SCREEN_H);
@@ -138,10 +158,12 @@ fn main() {
         force_menu_open(&mut menu, 1);
         let mut popup = Popup::new("While In Menu".to_string(), "Achievement while menu is open".to_string());
         popup.force_hold();
+        let queue = PopupQueue::with_visible(vec![popup]);
         let state = FrameState {
-            popup: Some(&popup),
+            popup: &queue,
             menu: Some(&menu),
             menu_config: &config.menu,
+            text_fit: &config.text_fit,
             game_name: Some("Chrono Trigger"),
         };
         let argb = rend.render_frame(&state, SCREEN_W, SCREEN_H);
@@ -213,6 +235,12 @@ fn main() {
     println!("\natlas: {} ({}x{}, {} frames)", atlas_path.display(), atlas_w, atlas_h, all_frames.len());
     println!("individual frames in {}/", out.display());
 
+    // ── Animated regression frames (--animate only) ─────────
+    if animate {
+        animate_toasts(&rend, &config, &game_bg, &out);
+        animate_menu_open(&rend, &config, &items, &game_bg, &out);
+    }
+
     #[cfg(target_os = "macos")]
     {
         let _ = std::process::Command::new("open").arg(&atlas_path).spawn();
@@ -255,6 +283,20 @@ fn generate_placeholder_badge(w: u32, h: u32) -> Vec<u8> {
 }
 
 fn save_argb_png(path: &std::path::Path, w: u32, h: u32, argb: &[u32]) {
+    save_rgba_png(path, w, h, &argb_to_rgba(argb));
+}
+
+fn save_rgba_png(path: &std::path::Path, w: u32, h: u32, rgba: &[u8]) {
+    let file = std::fs::File::create(path).expect("create png");
+    let buf = std::io::BufWriter::new(file);
+    let mut encoder = png::Encoder::new(buf, w, h);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().expect("png header");
+    writer.write_image_data(rgba).expect("png data");
+}
+
+fn argb_to_rgba(argb: &[u32]) -> Vec<u8> {
     let mut rgba = Vec::with_capacity(argb.len() * 4);
     for &pixel in argb {
         rgba.push(((pixel >> 16) & 0xFF) as u8);
@@ -262,17 +304,85 @@ fn save_argb_png(path: &std::path::Path, w: u32, h: u32, argb: &[u32]) {
         rgba.push((pixel & 0xFF) as u8);
         rgba.push(((pixel >> 24) & 0xFF) as u8);
     }
-    save_rgba_png(path, w, h, &rgba);
+    rgba
 }
 
-fn save_rgba_png(path: &std::path::Path, w: u32, h: u32, rgba: &[u8]) {
-    let file = std::fs::File::create(path).expect("create png");
+/// Write an animated PNG from a sequence of equally-sized ARGB frames, each
+/// shown for `delay_ms`. Loops forever (`num_plays: 0`).
+fn save_animated_argb_png(path: &std::path::Path, w: u32, h: u32, frames: &[Vec<u32>], delay_ms: u16) {
+    let file = std::fs::File::create(path).expect("create apng");
     let buf = std::io::BufWriter::new(file);
     let mut encoder = png::Encoder::new(buf, w, h);
     encoder.set_color(png::ColorType::Rgba);
     encoder.set_depth(png::BitDepth::Eight);
-    let mut writer = encoder.write_header().expect("png header");
-    writer.write_image_data(rgba).expect("png data");
+    encoder.set_animated(frames.len() as u32, 0).expect("set_animated");
+    encoder.set_frame_delay(delay_ms, 1000).expect("set_frame_delay");
+    let mut writer = encoder.write_header().expect("apng header");
+    for argb in frames {
+        writer.write_image_data(&argb_to_rgba(argb)).expect("apng frame data");
+    }
+    writer.finish().expect("apng finish");
+}
+
+/// Drive each toast scenario through its real slide-in/hold/fade-out timing
+/// (shortened so the clip stays short) and write an APNG per scenario —
+/// a regression tool for `Popup::tick`'s time-based behavior.
+fn animate_toasts(rend: &Renderer, config: &OverlayConfig, game_bg: &[u32], out: &std::path::Path) {
+    let toasts = [
+        ("First Blood", "Defeat the first enemy"),
+        ("Dragon Slayer Supreme", "A very long description that should truncate with an ellipsis automatically"),
+    ];
+
+    for (i, (title, desc)) in toasts.iter().enumerate() {
+        let popup = Popup::new(title.to_string(), desc.to_string()).with_hold_ms(ANIMATE_DEMO_HOLD_MS);
+        let mut queue = PopupQueue::new();
+        queue.push(popup);
+
+        let mut frames = Vec::with_capacity(ANIMATE_FRAMES as usize);
+        for _ in 0..ANIMATE_FRAMES {
+            let state = FrameState {
+                popup: &queue,
+                menu: None,
+                menu_config: &config.menu,
+                text_fit: &config.text_fit,
+                game_name: None,
+            };
+            let argb = rend.render_frame(&state, SCREEN_W, SCREEN_H);
+            frames.push(composite_over_bg(game_bg, &argb, SCREEN_W, SCREEN_H));
+            queue.tick(Duration::from_millis(ANIMATE_DT_MS));
+        }
+
+        let path = out.join(format!("anim-toast-{}.png", i));
+        save_animated_argb_png(&path, SCREEN_W, SCREEN_H, &frames, ANIMATE_DT_MS as u16);
+        println!("animated: {}", path.display());
+    }
+}
+
+/// Drive the menu's real open transition (`Menu::toggle` + `tick`) and write
+/// an APNG — a regression tool for `Menu`'s opacity/scale easing.
+fn animate_menu_open(rend: &Renderer, config: &OverlayConfig, items: &[superkonna_overlay::config::MenuItem], game_bg: &[u32], out: &std::path::Path) {
+    let mut menu = Menu::new(items.to_vec());
+    menu.toggle();
+    let no_toasts = PopupQueue::new();
+
+    let mut frames = Vec::with_capacity(ANIMATE_FRAMES as usize);
+    for _ in 0..ANIMATE_FRAMES {
+        let state = FrameState {
+            popup: &no_toasts,
+            menu: Some(&menu),
+            menu_config: &config.menu,
+            text_fit: &config.text_fit,
+            game_name: Some("Super Mario World"),
+        };
+        let argb = rend.render_frame(&state, SCREEN_W, SCREEN_H);
+        frames.push(composite_over_bg(game_bg, &argb, SCREEN_W, SCREEN_H));
+        menu.tick();
+        std::thread::sleep(Duration::from_millis(ANIMATE_DT_MS));
+    }
+
+    let path = out.join("anim-menu-open.png");
+    save_animated_argb_png(&path, SCREEN_W, SCREEN_H, &frames, ANIMATE_DT_MS as u16);
+    println!("animated: {}", path.display());
 }
 
 /// Generate a fake game screenshot background (dark gradient with some color).
@@ -8,10 +8,82 @@
 use crate::buttons::Button;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use log::{info, warn};
 
+/// Editors often write a file twice in quick succession (truncate, then
+/// write) — coalesce a burst of change notifications into a single reload.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// The built-in bindings, used when no `bindings.toml` is found on disk
+/// (see `Bindings::builtin_default`). Mirrors the standard ES quick-menu
+/// layout: A confirms (labelled "Select"), the Select button goes back, B
+/// resumes, Y/X hold to save/load state, and a
+/// held Start (with a confirmation) quits to EmulationStation.
+const DEFAULT_BINDINGS: &str = r#"
+[defaults]
+hold_ms = 1500
+
+[actions.confirm]
+label = "Select"
+button = "a"
+
+[actions.back]
+label = "Back"
+button = "select"
+
+[actions.resume]
+label = "Resume"
+button = "b"
+
+[actions.save_state]
+label = "Save State"
+button = "y"
+hold = true
+
+[actions.load_state]
+label = "Load State"
+button = "x"
+hold = true
+
+[actions.quit_to_es]
+label = "Quit to EmulationStation"
+button = "start"
+hold = true
+hold_ms = 2000
+confirm = true
+
+[[menu]]
+id = "resume"
+label = "Resume"
+action_type = "dismiss"
+bind_action = "resume"
+
+[[menu]]
+id = "save_state"
+label = "Save State"
+action_type = "retroarch"
+command = "SAVE_STATE"
+bind_action = "save_state"
+
+[[menu]]
+id = "load_state"
+label = "Load State"
+action_type = "retroarch"
+command = "LOAD_STATE"
+bind_action = "load_state"
+
+[[menu]]
+id = "quit_to_es"
+label = "Quit to EmulationStation"
+action_type = "retroarch"
+command = "QUIT"
+bind_action = "quit_to_es"
+"#;
+
 // ── TOML schema ─────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
@@ -39,8 +111,13 @@ impl Default for BindingDefaults {
 #[derive(Debug, Deserialize)]
 struct ActionDef {
     label: String,
+    /// `"a"` for a bare binding, or `"l1+a"` for a chord — the last
+    /// `+`-separated token is the trigger, everything before it an
+    /// additional modifier (combined with `modifiers`, if also given).
     button: String,
     #[serde(default)]
+    modifiers: Vec<String>,
+    #[serde(default)]
     hold: bool,
     hold_ms: Option<u64>,
     #[serde(default)]
@@ -59,15 +136,52 @@ struct MenuItemDef {
 
 fn default_hold_ms() -> u64 { 1500 }
 
+/// Parse a `button` field together with an optional explicit `modifiers`
+/// list into a `Chord`. `None` if the trigger (or any modifier) isn't a
+/// recognized button name.
+fn parse_chord(button: &str, modifiers: &[String]) -> Option<Chord> {
+    let mut parts: Vec<&str> = button.split('+').map(str::trim).collect();
+    let trigger = Button::from_name(parts.pop()?)?;
+
+    let mut mods = Vec::new();
+    for name in parts.into_iter().chain(modifiers.iter().map(String::as_str)) {
+        mods.push(Button::from_name(name)?);
+    }
+    mods.dedup();
+
+    Some(Chord { modifiers: mods, trigger })
+}
+
 // ── Public types ────────────────────────────────────────────
 
+/// A trigger button plus the modifier buttons that must also be held,
+/// the way window managers express keybindings (a modifier set plus a
+/// trigger). A bare single-button binding is a `Chord` with no modifiers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chord {
+    pub modifiers: Vec<Button>,
+    pub trigger: Button,
+}
+
+impl Chord {
+    /// True if every button this chord needs is present in `held`.
+    pub fn matches(&self, held: &[Button]) -> bool {
+        held.contains(&self.trigger) && self.modifiers.iter().all(|m| held.contains(m))
+    }
+}
+
 /// A resolved semantic action with its binding.
 #[derive(Debug, Clone)]
 pub struct Action {
     pub name: String,
     pub label: String,
+    /// The trigger button, for code that only cares about that (equal to
+    /// `chord.trigger`).
     pub button: Button,
+    /// The raw `button` string from the TOML (e.g. `"b"` or `"l1+a"`),
+    /// kept for display and legacy `MenuItem` bind strings.
     pub button_name: String,
+    pub chord: Chord,
     pub hold: bool,
     pub hold_ms: u64,
     pub confirm: bool,
@@ -87,11 +201,61 @@ pub struct BoundMenuItem {
 }
 
 /// The full resolved bindings config.
+#[derive(Clone)]
 pub struct Bindings {
     pub actions: HashMap<String, Action>,
     pub menu_items: Vec<BoundMenuItem>,
 }
 
+/// A shared, hot-reloadable handle around a `Bindings`, kept in sync with
+/// `bindings.toml` by `Bindings::watch`'s background thread. Cloning is a
+/// cheap `Arc` bump — every clone observes the latest successfully-reloaded
+/// config.
+#[derive(Clone)]
+pub struct BindingsHandle {
+    inner: Arc<Mutex<Bindings>>,
+}
+
+impl BindingsHandle {
+    /// Snapshot of the currently active bindings.
+    pub fn current(&self) -> Bindings {
+        self.inner.lock().unwrap().clone()
+    }
+}
+
+/// Block watching `path` for changes, reloading and swapping the bindings
+/// behind `handle` on each one (debounced — see `RELOAD_DEBOUNCE`).
+fn run_watch(path: &Path, handle: BindingsHandle, on_reload: impl Fn(&Bindings)) -> Result<(), String> {
+    use notify::{RecursiveMode, Watcher};
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })
+    .map_err(|e| format!("bindings watcher: {e}"))?;
+
+    watcher.watch(path, RecursiveMode::NonRecursive).map_err(|e| format!("bindings watch: {e}"))?;
+    info!("Watching {} for changes", path.display());
+
+    loop {
+        rx.recv().map_err(|_| "bindings watcher channel closed".to_string())?;
+        // Drain anything else that arrives within the debounce window so a
+        // double-write only triggers one reload.
+        while rx.recv_timeout(RELOAD_DEBOUNCE).is_ok() {}
+
+        match Bindings::load(path) {
+            Ok(new_bindings) => {
+                info!("Reloaded bindings from {}", path.display());
+                *handle.inner.lock().unwrap() = new_bindings;
+                on_reload(&handle.current());
+            }
+            Err(e) => warn!("Failed to reload {}: {e} — keeping prior bindings", path.display()),
+        }
+    }
+}
+
 impl Bindings {
     /// Load and resolve bindings from a TOML file.
     pub fn load(path: &Path) -> Result<Self, String> {
@@ -104,13 +268,7 @@ impl Bindings {
 
     /// Search for bindings.toml in standard locations.
     pub fn find_and_load(theme_root: &Path) -> Self {
-        let candidates = vec![
-            std::env::var("SUPERKONNA_BINDINGS").ok().map(std::path::PathBuf::from),
-            Some(std::path::PathBuf::from("/userdata/system/superkonna-overlay/bindings.toml")),
-            Some(theme_root.join("bindings.toml")),
-        ];
-
-        for path in candidates.into_iter().flatten() {
+        for path in Self::candidate_paths(theme_root) {
             if path.exists() {
                 match Self::load(&path) {
                     Ok(b) => {
@@ -126,17 +284,59 @@ impl Bindings {
         Self::builtin_default()
     }
 
+    /// Standard bindings.toml locations, in priority order (same order
+    /// `find_and_load` checks).
+    fn candidate_paths(theme_root: &Path) -> Vec<PathBuf> {
+        vec![
+            std::env::var("SUPERKONNA_BINDINGS").ok().map(PathBuf::from),
+            Some(PathBuf::from("/userdata/system/superkonna-overlay/bindings.toml")),
+            Some(theme_root.join("bindings.toml")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Load bindings the same way `find_and_load` does, then keep watching
+    /// the first candidate path that exists on disk for changes (via
+    /// `notify`), re-resolving and swapping the bindings behind the
+    /// returned handle on each one. A file that fails to parse is logged
+    /// and the prior good config is kept. `on_reload` is called with the
+    /// newly-active bindings after each successful swap, so callers can
+    /// rebuild anything derived from them (the menu item list, hint bar).
+    /// Spawns a background thread; does not block. If no bindings.toml
+    /// exists on disk (built-in defaults in use), there's nothing to watch
+    /// and the handle just never reloads.
+    pub fn watch(theme_root: &Path, on_reload: impl Fn(&Bindings) + Send + 'static) -> BindingsHandle {
+        let handle = BindingsHandle { inner: Arc::new(Mutex::new(Self::find_and_load(theme_root))) };
+
+        let Some(path) = Self::candidate_paths(theme_root).into_iter().find(|p| p.exists()) else {
+            info!("No bindings.toml on disk — hot-reload disabled, using built-in defaults");
+            return handle;
+        };
+
+        let watched = handle.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = run_watch(&path, watched, on_reload) {
+                warn!("bindings watcher exited: {e}");
+            }
+        });
+
+        handle
+    }
+
     fn resolve(file: BindingsFile) -> Self {
         let default_hold = file.defaults.hold_ms;
 
-        let actions: HashMap<String, Action> = file.actions.into_iter()
+        let mut actions: HashMap<String, Action> = file.actions.into_iter()
             .filter_map(|(name, def)| {
-                let button = Button::from_name(&def.button)?;
+                let chord = parse_chord(&def.button, &def.modifiers)?;
                 Some((name.clone(), Action {
                     name: name.clone(),
                     label: def.label,
-                    button,
+                    button: chord.trigger,
                     button_name: def.button,
+                    chord,
                     hold: def.hold,
                     hold_ms: def.hold_ms.unwrap_or(default_hold),
                     confirm: def.confirm,
@@ -144,6 +344,21 @@ impl Bindings {
             })
             .collect();
 
+        // A chord's trigger button dispatching to it ambiguously with a bare
+        // single-button binding on the same trigger — drop the chord rather
+        // than guess which one the user meant.
+        let ambiguous: Vec<String> = actions.values()
+            .filter(|a| !a.chord.modifiers.is_empty())
+            .filter(|a| actions.values().any(|other| {
+                other.name != a.name && other.chord.modifiers.is_empty() && other.chord.trigger == a.chord.trigger
+            }))
+            .map(|a| a.name.clone())
+            .collect();
+        for name in ambiguous {
+            warn!("bindings: action '{name}' is a chord whose trigger also has a bare binding — dropping it to avoid ambiguous dispatch");
+            actions.remove(&name);
+        }
+
         let menu_items: Vec<BoundMenuItem> = file.menu.into_iter()
             .map(|item| {
                 let binding = item.bind_action.as_ref()
@@ -164,10 +379,13 @@ impl Bindings {
         Bindings { actions, menu_items }
     }
 
-    fn builtin_default() -> Self {
-        let toml_str = include_str!("../../../bindings.toml");
-        let file: BindingsFile = toml::from_str(toml_str)
-            .expect("built-in bindings.toml must be valid");
+    /// Built-in bindings used when no `bindings.toml` is found on disk.
+    /// Embedded as a literal (rather than `include_str!`ing a theme-root
+    /// file at compile time) so the crate builds standalone, with no
+    /// dependency on an asset living at a fixed path relative to this file.
+    pub fn builtin_default() -> Self {
+        let file: BindingsFile = toml::from_str(DEFAULT_BINDINGS)
+            .expect("DEFAULT_BINDINGS must be valid");
         Self::resolve(file)
     }
 
@@ -181,14 +399,21 @@ impl Bindings {
         self.actions.values().filter(|a| a.hold).collect()
     }
 
-    /// Get the action bound to a specific button press (non-hold).
-    pub fn press_action_for(&self, button: Button) -> Option<&Action> {
-        self.actions.values().find(|a| a.button == button && !a.hold)
+    /// Get the press (non-hold) action whose chord is satisfied by the
+    /// currently held button set, preferring the most specific (most
+    /// modifiers) match when more than one chord is satisfied at once.
+    pub fn press_action_for(&self, held: &[Button]) -> Option<&Action> {
+        self.actions.values()
+            .filter(|a| !a.hold && a.chord.matches(held))
+            .max_by_key(|a| a.chord.modifiers.len())
     }
 
-    /// Get the action bound to a specific button hold.
-    pub fn hold_action_for(&self, button: Button) -> Option<&Action> {
-        self.actions.values().find(|a| a.button == button && a.hold)
+    /// Get the hold action whose chord is satisfied by the currently held
+    /// button set, preferring the most specific (most modifiers) match.
+    pub fn hold_action_for(&self, held: &[Button]) -> Option<&Action> {
+        self.actions.values()
+            .filter(|a| a.hold && a.chord.matches(held))
+            .max_by_key(|a| a.chord.modifiers.len())
     }
 
     /// Get hints for the hint bar: (button, label, is_hold) tuples,
@@ -200,6 +425,7 @@ impl Bindings {
         if let Some(a) = self.actions.get("confirm") {
             hints.push(HintBarItem {
                 button: a.button,
+                modifiers: a.chord.modifiers.clone(),
                 label: a.label.clone(),
                 hold: false,
                 hold_ms: 0,
@@ -212,6 +438,7 @@ impl Bindings {
                 if binding.name == "confirm" { continue; }
                 hints.push(HintBarItem {
                     button: binding.button,
+                    modifiers: binding.chord.modifiers.clone(),
                     label: binding.label.clone(),
                     hold: binding.hold,
                     hold_ms: binding.hold_ms,
@@ -222,28 +449,48 @@ impl Bindings {
         hints
     }
 
-    /// Convert to legacy MenuItem vec for the Menu state machine.
-    pub fn to_menu_items(&self) -> Vec<crate::config::MenuItem> {
+    /// Convert to legacy MenuItem vec for the Menu state machine. Errors
+    /// (naming the offending item id) if any `action_type` isn't a known
+    /// `ActionKind` — a resolve-time error, consistent with `ActionKind`'s
+    /// own config-load-time validation.
+    pub fn to_menu_items(&self) -> Result<Vec<crate::config::MenuItem>, String> {
         self.menu_items.iter().map(|item| {
-            let (bind, hold_bind, hold_ms) = match &item.binding {
-                Some(b) if b.hold => (None, Some(b.button_name.clone()), b.hold_ms),
-                Some(b) => (Some(b.button_name.clone()), None, 1500),
-                None => (None, None, 1500),
+            // `bind`/`hold_bind` always key on the trigger button alone (the
+            // hold timer in `Menu` is per-trigger); `chord` is the extra
+            // "these modifiers must also be held" condition layered on top,
+            // set only for an actual chord binding.
+            let (bind, hold_bind, chord, hold_ms) = match &item.binding {
+                Some(b) if b.hold => {
+                    let trigger = config_button_name(b.chord.trigger).to_string();
+                    let chord = (!b.chord.modifiers.is_empty()).then(|| b.button_name.clone());
+                    (None, Some(trigger), chord, b.hold_ms)
+                }
+                Some(b) => {
+                    let trigger = config_button_name(b.chord.trigger).to_string();
+                    let chord = (!b.chord.modifiers.is_empty()).then(|| b.button_name.clone());
+                    (Some(trigger), None, chord, 1500)
+                }
+                None => (None, None, None, 1500),
             };
-            crate::config::MenuItem {
+            let action: crate::config::ActionKind = item.action_type.parse()
+                .map_err(|e| format!("menu item '{}': {e}", item.id))?;
+            Ok(crate::config::MenuItem {
                 id: item.id.clone(),
                 label: item.label.clone(),
                 icon: item.icon.clone(),
-                action: item.action_type.clone(),
+                action,
                 command: item.command.clone(),
                 confirm: item.confirm,
                 bind,
                 hold_bind,
+                chord,
                 hold_ms,
                 hint_label: Some(item.binding.as_ref()
                     .map(|b| b.label.clone())
                     .unwrap_or_else(|| item.label.clone())),
-            }
+                visible_if: None,
+                items: Vec::new(),
+            })
         }).collect()
     }
 }
@@ -252,6 +499,9 @@ impl Bindings {
 #[derive(Debug, Clone)]
 pub struct HintBarItem {
     pub button: Button,
+    /// Modifier buttons that must also be held, for a chord binding.
+    /// Empty for a bare binding.
+    pub modifiers: Vec<Button>,
     pub label: String,
     pub hold: bool,
     pub hold_ms: u64,
@@ -260,22 +510,36 @@ pub struct HintBarItem {
 impl HintBarItem {
     /// Return the button name as used in config (for hold_progress lookup).
     pub fn button_name_for_config(&self) -> String {
-        match self.button {
-            Button::A => "a",
-            Button::B => "b",
-            Button::X => "x",
-            Button::Y => "y",
-            Button::LB => "l1",
-            Button::RB => "r1",
-            Button::LT => "l2",
-            Button::RT => "r2",
-            Button::Start => "start",
-            Button::Select => "select",
-            Button::DpadUp => "up",
-            Button::DpadDown => "down",
-            Button::DpadLeft => "left",
-            Button::DpadRight => "right",
-        }.to_string()
+        config_button_name(self.button).to_string()
+    }
+
+    /// The glyphs to draw for this hint, modifiers first then the trigger
+    /// (e.g. `["l1", "a"]` for a `l1+a` chord, or just `["b"]` for a bare
+    /// binding) — the renderer draws one icon per name and joins them.
+    pub fn chord_button_names(&self) -> Vec<String> {
+        self.modifiers.iter().map(|&b| config_button_name(b).to_string())
+            .chain(std::iter::once(config_button_name(self.button).to_string()))
+            .collect()
+    }
+}
+
+/// The config/legacy button name for a `Button` (e.g. `Button::LB` -> `"l1"`).
+fn config_button_name(button: Button) -> &'static str {
+    match button {
+        Button::A => "a",
+        Button::B => "b",
+        Button::X => "x",
+        Button::Y => "y",
+        Button::LB => "l1",
+        Button::RB => "r1",
+        Button::LT => "l2",
+        Button::RT => "r2",
+        Button::Start => "start",
+        Button::Select => "select",
+        Button::DpadUp => "up",
+        Button::DpadDown => "down",
+        Button::DpadLeft => "left",
+        Button::DpadRight => "right",
     }
 }
 
@@ -327,7 +591,7 @@ mod tests {
     #[test]
     fn to_legacy_menu_items() {
         let bindings = Bindings::builtin_default();
-        let items = bindings.to_menu_items();
+        let items = bindings.to_menu_items().unwrap();
         assert!(!items.is_empty());
 
         let save = items.iter().find(|i| i.id == "save_state").unwrap();
@@ -338,4 +602,72 @@ mod tests {
         assert_eq!(resume.bind.as_deref(), Some("b"));
         assert!(resume.hold_bind.is_none());
     }
+
+    #[test]
+    fn parse_chord_splits_modifiers_from_trigger() {
+        let chord = parse_chord("l1+a", &[]).unwrap();
+        assert_eq!(chord.trigger, Button::A);
+        assert_eq!(chord.modifiers, vec![Button::LB]);
+
+        let bare = parse_chord("a", &[]).unwrap();
+        assert_eq!(bare.trigger, Button::A);
+        assert!(bare.modifiers.is_empty());
+
+        let explicit = parse_chord("a", &["l1".to_string(), "r1".to_string()]).unwrap();
+        assert_eq!(explicit.trigger, Button::A);
+        assert_eq!(explicit.modifiers, vec![Button::LB, Button::RB]);
+    }
+
+    #[test]
+    fn chord_matches_requires_all_modifiers_held() {
+        let chord = parse_chord("l1+a", &[]).unwrap();
+        assert!(chord.matches(&[Button::LB, Button::A]));
+        assert!(!chord.matches(&[Button::A]));
+        assert!(!chord.matches(&[Button::LB]));
+    }
+
+    #[test]
+    fn press_action_for_prefers_more_specific_chord() {
+        // Neither binding is a bare single-button binding on "a", so both
+        // survive resolution; the more specific (2-modifier) chord should
+        // win when both are satisfied at once.
+        let toml_str = r#"
+            [actions.menu_a]
+            label = "Menu A"
+            button = "l1+a"
+
+            [actions.menu_b]
+            label = "Menu B"
+            button = "l1+r1+a"
+        "#;
+        let file: BindingsFile = toml::from_str(toml_str).unwrap();
+        let bindings = Bindings::resolve(file);
+        assert!(bindings.actions.contains_key("menu_a"));
+        assert!(bindings.actions.contains_key("menu_b"));
+
+        let held = [Button::LB, Button::RB, Button::A];
+        let action = bindings.press_action_for(&held).unwrap();
+        assert_eq!(action.name, "menu_b");
+    }
+
+    #[test]
+    fn chord_ambiguous_with_bare_binding_is_dropped() {
+        // "jump" binds trigger "a" bare; "turbo_jump" binds "l1+a" — both
+        // dispatch off the same trigger, so resolution can't tell which one
+        // a bare press of "a" (with l1 also incidentally held) should mean.
+        // The chord loses.
+        let toml_str = r#"
+            [actions.jump]
+            label = "Jump"
+            button = "a"
+
+            [actions.turbo_jump]
+            label = "Turbo Jump"
+            button = "l1+a"
+        "#;
+        let file: BindingsFile = toml::from_str(toml_str).unwrap();
+        let bindings = Bindings::resolve(file);
+        assert!(bindings.actions.contains_key("jump"));
+        assert!(!bindings.actions.contains_key("turbo_jump"));
+    }
 }
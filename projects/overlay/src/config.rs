@@ -8,6 +8,130 @@ use log::{info, warn};
 #[derive(Debug, Deserialize)]
 pub struct OverlayConfig {
     pub menu: MenuConfig,
+    #[serde(default)]
+    pub sounds: SoundConfig,
+    #[serde(default)]
+    pub toasts: ToastConfig,
+    #[serde(default)]
+    pub retroachievements: RetroAchievementsConfig,
+    #[serde(default)]
+    pub text_fit: TextFitConfig,
+    #[serde(default)]
+    pub dbus: DbusConfig,
+}
+
+/// Achievement toast stacking behavior.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToastConfig {
+    /// How many toasts can be on screen at once before new ones queue.
+    #[serde(default = "default_max_visible_toasts")]
+    pub max_visible: usize,
+}
+
+impl Default for ToastConfig {
+    fn default() -> Self {
+        ToastConfig { max_visible: default_max_visible_toasts() }
+    }
+}
+
+fn default_max_visible_toasts() -> usize { 1 }
+
+/// Per-event sound cue paths for the mixer-based `sound` module.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SoundConfig {
+    #[serde(default = "default_sound_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_sound_volume")]
+    pub volume: f32,
+    /// Played when an achievement toast appears.
+    pub toast: Option<String>,
+    /// Played when the cursor moves in the quick menu.
+    pub menu_move: Option<String>,
+    /// Played when a quick menu item is selected (also used for menu open).
+    pub menu_select: Option<String>,
+    /// Played when backing out of the quick menu or a submenu.
+    pub menu_back: Option<String>,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        SoundConfig {
+            enabled: default_sound_enabled(),
+            volume: default_sound_volume(),
+            toast: Some("toast.wav".into()),
+            menu_move: Some("scroll.wav".into()),
+            menu_select: Some("confirm.wav".into()),
+            menu_back: Some("back.wav".into()),
+        }
+    }
+}
+
+fn default_sound_enabled() -> bool { true }
+fn default_sound_volume() -> f32 { 0.8 }
+
+/// Optional RetroAchievements Web API credentials. When both fields are set,
+/// `ra_api` fetches real achievement metadata and badge art for unlock
+/// popups instead of relying solely on the text scraped from the RetroArch
+/// log; otherwise popups stay text-only, same as today.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RetroAchievementsConfig {
+    pub username: Option<String>,
+    pub api_key: Option<String>,
+}
+
+impl RetroAchievementsConfig {
+    pub fn is_configured(&self) -> bool {
+        self.username.is_some() && self.api_key.is_some()
+    }
+}
+
+/// Controls how `renderer::fit_text` handles titles/labels too wide for
+/// their box: ellipsis-truncate, or marquee-scroll once the overflow
+/// exceeds `marquee_threshold`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TextFitConfig {
+    /// Whether overflowing text marquee-scrolls at all; when `false` it's
+    /// always ellipsis-truncated instead, regardless of overflow amount.
+    #[serde(default = "default_marquee_enabled")]
+    pub marquee_enabled: bool,
+    /// Scroll speed in pixels per second.
+    #[serde(default = "default_marquee_speed")]
+    pub marquee_speed: f32,
+    /// Overflow (text width minus box width, in pixels) below which text is
+    /// ellipsis-truncated rather than marqueed.
+    #[serde(default = "default_marquee_threshold")]
+    pub marquee_threshold: f32,
+    /// How long the marquee dwells at each end of its scroll before looping.
+    #[serde(default = "default_marquee_pause_ms")]
+    pub marquee_pause_ms: u64,
+}
+
+impl Default for TextFitConfig {
+    fn default() -> Self {
+        TextFitConfig {
+            marquee_enabled: default_marquee_enabled(),
+            marquee_speed: default_marquee_speed(),
+            marquee_threshold: default_marquee_threshold(),
+            marquee_pause_ms: default_marquee_pause_ms(),
+        }
+    }
+}
+
+fn default_marquee_enabled() -> bool { true }
+fn default_marquee_speed() -> f32 { 40.0 }
+fn default_marquee_threshold() -> f32 { 24.0 }
+fn default_marquee_pause_ms() -> u64 { 800 }
+
+/// Optional D-Bus control interface (see `dbus` module) — exposes the same
+/// command surface as the Unix socket as typed methods/signals for desktop
+/// tools. Off by default since most Batocera setups have no bus running.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DbusConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Use the system bus instead of the session bus.
+    #[serde(default)]
+    pub system_bus: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -24,13 +148,15 @@ pub struct MenuConfig {
     pub padding: u16,
     #[serde(default = "default_corner_radius")]
     pub corner_radius: f32,
-    pub sound_scroll: Option<String>,
-    pub sound_select: Option<String>,
-    pub sound_back: Option<String>,
+    /// Explicit override for the device-scale factor (see
+    /// `renderer::device_scale`). When unset, the renderer derives it from
+    /// `screen_h` against a 1080p baseline, so most themes never set this.
+    #[serde(default)]
+    pub ui_scale: Option<f32>,
     #[serde(default)]
     pub retroarch: RetroArchConfig,
     #[serde(default = "default_items")]
-    pub items: Vec<MenuItem>,
+    pub items: Vec<MenuEntry>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -50,12 +176,44 @@ impl Default for RetroArchConfig {
     }
 }
 
+/// What a menu item's `action` does, parsed from its TOML string at
+/// config-load time. Unknown values surface as a load error instead of
+/// silently producing a dead item that `Menu::execute_item` can't dispatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ActionKind {
+    #[serde(rename = "dismiss")]
+    Dismiss,
+    #[serde(rename = "retroarch")]
+    RetroArch,
+    #[serde(rename = "shell")]
+    Shell,
+    #[serde(rename = "submenu")]
+    Submenu,
+}
+
+impl std::str::FromStr for ActionKind {
+    type Err = String;
+
+    /// Parse the same strings `#[serde(rename = ...)]` accepts above — used
+    /// by code that builds a `MenuItem` directly in Rust (e.g.
+    /// `bindings::Bindings::to_menu_items`) rather than through serde.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dismiss" => Ok(ActionKind::Dismiss),
+            "retroarch" => Ok(ActionKind::RetroArch),
+            "shell" => Ok(ActionKind::Shell),
+            "submenu" => Ok(ActionKind::Submenu),
+            other => Err(format!("unknown action kind '{other}'")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct MenuItem {
     pub id: String,
     pub label: String,
     pub icon: Option<String>,
-    pub action: String,
+    pub action: ActionKind,
     pub command: Option<String>,
     #[serde(default)]
     pub confirm: bool,
@@ -63,15 +221,117 @@ pub struct MenuItem {
     pub bind: Option<String>,
     /// Hold-for-duration shortcut button (e.g. "y" for Save State).
     pub hold_bind: Option<String>,
+    /// Chord form of `bind`/`hold_bind`, e.g. `"l1+a"` (last token is the
+    /// trigger, everything before it a required modifier). Set alongside
+    /// `bind`/`hold_bind`, which still name the trigger button; `Menu` then
+    /// also requires every modifier to be in the held-button set.
+    #[serde(default)]
+    pub chord: Option<String>,
     /// Hold duration in ms (default 1500).
     #[serde(default = "default_hold_ms")]
     pub hold_ms: u64,
     /// Short label shown in hint bar (defaults to label if absent).
     pub hint_label: Option<String>,
+    /// Shell predicate gating visibility: run via `sh -c` on menu open, exit
+    /// code 0 shows the item, anything else hides it. `None` always shows.
+    /// Evaluated off the UI thread; the item stays visible until the first
+    /// result comes back (see `Menu::refresh_visibility`).
+    pub visible_if: Option<String>,
+    /// Child entries for a submenu (`action = "submenu"`). Selecting this
+    /// item pushes the current item list and enters `items` as the new
+    /// level; empty for a plain action. Declared as nested
+    /// `[[menu.items.items]]` tables in TOML, recursively — a child can
+    /// itself be a submenu.
+    #[serde(default)]
+    pub items: Vec<MenuEntry>,
 }
 
 fn default_hold_ms() -> u64 { 1500 }
 
+/// A non-selectable section label. Rendered smaller and dimmer than items;
+/// skipped by cursor navigation.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HeaderEntry {
+    pub header: String,
+}
+
+/// Non-selectable vertical gap, in px before scaling.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpacerEntry {
+    pub spacer: f32,
+}
+
+/// A greyed-out entry shown for context but not reachable by the cursor
+/// (e.g. an action unavailable in the current state).
+#[derive(Debug, Clone, Deserialize)]
+pub struct DisabledEntry {
+    pub disabled: String,
+}
+
+/// An on/off quick-settings control, rendered as a capsule.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToggleEntry {
+    pub toggle: String,
+    #[serde(default)]
+    pub value: bool,
+}
+
+/// A cycling quick-settings control, rendered as `‹ value ›`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OptionCycleEntry {
+    pub option_cycle: String,
+    #[serde(default)]
+    pub selected: usize,
+    pub options: Vec<String>,
+}
+
+/// A 0.0..=1.0 quick-settings control, rendered as a filled track.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SliderEntry {
+    pub slider: String,
+    #[serde(default)]
+    pub value: f32,
+}
+
+/// One entry in the quick menu's item list.
+///
+/// `Action` is the original (and only historically-supported) shape — a
+/// plain selectable item with an `id`/`label`/`action`. The others are
+/// quick-settings and layout entries; each is tagged implicitly by which
+/// field is present in its TOML/JSON table, so existing `[[menu.items]]`
+/// configs keep parsing unchanged.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum MenuEntry {
+    Action(MenuItem),
+    Toggle(ToggleEntry),
+    OptionCycle(OptionCycleEntry),
+    Slider(SliderEntry),
+    Header(HeaderEntry),
+    Spacer(SpacerEntry),
+    Disabled(DisabledEntry),
+}
+
+impl MenuEntry {
+    /// Whether cursor navigation can land on this entry.
+    pub fn is_selectable(&self) -> bool {
+        !matches!(self, MenuEntry::Header(_) | MenuEntry::Spacer(_) | MenuEntry::Disabled(_))
+    }
+
+    /// The text label shown for this entry (empty for spacers).
+    pub fn label(&self) -> &str {
+        match self {
+            MenuEntry::Action(item) => &item.label,
+            MenuEntry::Toggle(t) => &t.toggle,
+            MenuEntry::OptionCycle(c) => &c.option_cycle,
+            MenuEntry::Slider(s) => &s.slider,
+            MenuEntry::Header(h) => &h.header,
+            MenuEntry::Disabled(d) => &d.disabled,
+            MenuEntry::Spacer(_) => "",
+        }
+    }
+}
+
 impl OverlayConfig {
     pub fn load(path: &Path) -> Result<Self, String> {
         let content = std::fs::read_to_string(path).map_err(|e| format!("read {}: {e}", path.display()))?;
@@ -120,12 +380,15 @@ impl OverlayConfig {
                 item_height: default_item_height(),
                 padding: default_padding(),
                 corner_radius: default_corner_radius(),
-                sound_scroll: Some("scroll.wav".into()),
-                sound_select: Some("confirm.wav".into()),
-                sound_back: Some("back.wav".into()),
+                ui_scale: None,
                 retroarch: RetroArchConfig::default(),
                 items: default_items(),
             },
+            sounds: SoundConfig::default(),
+            toasts: ToastConfig::default(),
+            retroachievements: RetroAchievementsConfig::default(),
+            text_fit: TextFitConfig::default(),
+            dbus: DbusConfig::default(),
         }
     }
 }
@@ -139,32 +402,32 @@ fn default_corner_radius() -> f32 { 16.0 }
 fn default_host() -> String { "127.0.0.1".into() }
 fn default_port() -> u16 { 55355 }
 
-fn default_items() -> Vec<MenuItem> {
+fn default_items() -> Vec<MenuEntry> {
     vec![
-        MenuItem {
+        MenuEntry::Action(MenuItem {
             id: "resume".into(), label: "Resume".into(), icon: Some("gamepad.svg".into()),
-            action: "dismiss".into(), command: None, confirm: false,
-            bind: Some("b".into()), hold_bind: None, hold_ms: default_hold_ms(),
-            hint_label: None,
-        },
-        MenuItem {
+            action: ActionKind::Dismiss, command: None, confirm: false,
+            bind: Some("b".into()), hold_bind: None, chord: None, hold_ms: default_hold_ms(),
+            hint_label: None, visible_if: None, items: Vec::new(),
+        }),
+        MenuEntry::Action(MenuItem {
             id: "save_state".into(), label: "Save State".into(), icon: Some("savestate.svg".into()),
-            action: "retroarch".into(), command: Some("SAVE_STATE".into()), confirm: false,
-            bind: None, hold_bind: Some("y".into()), hold_ms: 1500,
-            hint_label: Some("Save".into()),
-        },
-        MenuItem {
+            action: ActionKind::RetroArch, command: Some("SAVE_STATE".into()), confirm: false,
+            bind: None, hold_bind: Some("y".into()), chord: None, hold_ms: 1500,
+            hint_label: Some("Save".into()), visible_if: None, items: Vec::new(),
+        }),
+        MenuEntry::Action(MenuItem {
             id: "load_state".into(), label: "Load State".into(), icon: Some("savestate.svg".into()),
-            action: "retroarch".into(), command: Some("LOAD_STATE".into()), confirm: false,
-            bind: None, hold_bind: Some("x".into()), hold_ms: 1500,
-            hint_label: Some("Load".into()),
-        },
-        MenuItem {
+            action: ActionKind::RetroArch, command: Some("LOAD_STATE".into()), confirm: false,
+            bind: None, hold_bind: Some("x".into()), chord: None, hold_ms: 1500,
+            hint_label: Some("Load".into()), visible_if: Some("ls /userdata/saves/*/*.state* >/dev/null 2>&1".into()), items: Vec::new(),
+        }),
+        MenuEntry::Action(MenuItem {
             id: "quit_to_es".into(), label: "Quit to EmulationStation".into(), icon: Some("exit-to-app.svg".into()),
-            action: "retroarch".into(), command: Some("QUIT".into()), confirm: true,
-            bind: None, hold_bind: Some("start".into()), hold_ms: 2000,
-            hint_label: Some("Quit".into()),
-        },
+            action: ActionKind::RetroArch, command: Some("QUIT".into()), confirm: true,
+            bind: None, hold_bind: Some("start".into()), chord: None, hold_ms: 2000,
+            hint_label: Some("Quit".into()), visible_if: None, items: Vec::new(),
+        }),
     ]
 }
 
@@ -202,16 +465,92 @@ confirm = true
         let config: OverlayConfig = toml::from_str(toml_str).unwrap();
         assert_eq!(config.menu.title, "TEST MENU");
         assert_eq!(config.menu.items.len(), 2);
-        assert_eq!(config.menu.items[0].action, "dismiss");
-        assert!(config.menu.items[1].confirm);
-        assert_eq!(config.menu.items[1].command.as_deref(), Some("QUIT"));
+        let MenuEntry::Action(resume) = &config.menu.items[0] else { panic!("expected Action") };
+        assert_eq!(resume.action, ActionKind::Dismiss);
+        let MenuEntry::Action(quit) = &config.menu.items[1] else { panic!("expected Action") };
+        assert!(quit.confirm);
+        assert_eq!(quit.command.as_deref(), Some("QUIT"));
+    }
+
+    #[test]
+    fn unknown_action_kind_is_a_parse_error() {
+        let err = "toggle_fast_forward".parse::<ActionKind>().unwrap_err();
+        assert!(err.contains("toggle_fast_forward"));
+    }
+
+    #[test]
+    fn unknown_action_is_a_load_error() {
+        let toml_str = r#"
+[menu]
+
+[[menu.items]]
+id = "bogus"
+label = "Bogus"
+action = "toggle_fast_forward"
+"#;
+        // `action` lives inside `MenuEntry::Action(MenuItem)`, and
+        // `MenuEntry` is `#[serde(untagged)]`: once every variant fails to
+        // match, serde reports only the generic untagged-enum error, not
+        // the specific `ActionKind` parse failure (see
+        // `unknown_action_kind_is_a_parse_error` above for that).
+        let err = toml::from_str::<OverlayConfig>(toml_str).unwrap_err();
+        assert!(err.to_string().contains("did not match any variant of untagged enum MenuEntry"));
     }
 
     #[test]
     fn builtin_default_has_four_items() {
         let config = OverlayConfig::builtin_default();
         assert_eq!(config.menu.items.len(), 4);
-        assert_eq!(config.menu.items[0].id, "resume");
-        assert_eq!(config.menu.items[3].id, "quit_to_es");
+        let MenuEntry::Action(first) = &config.menu.items[0] else { panic!("expected Action") };
+        assert_eq!(first.id, "resume");
+        let MenuEntry::Action(last) = &config.menu.items[3] else { panic!("expected Action") };
+        assert_eq!(last.id, "quit_to_es");
+    }
+
+    #[test]
+    fn parse_rich_entry_kinds() {
+        let toml_str = r#"
+[menu]
+
+[[menu.items]]
+header = "Display"
+
+[[menu.items]]
+toggle = "Fullscreen"
+value = true
+
+[[menu.items]]
+option_cycle = "Aspect Ratio"
+selected = 1
+options = ["4:3", "16:9", "Stretch"]
+
+[[menu.items]]
+slider = "Brightness"
+value = 0.75
+
+[[menu.items]]
+spacer = 8.0
+
+[[menu.items]]
+disabled = "Offline Mode"
+
+[[menu.items]]
+id = "resume"
+label = "Resume"
+action = "dismiss"
+"#;
+        let config: OverlayConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.menu.items.len(), 7);
+        assert!(matches!(config.menu.items[0], MenuEntry::Header(_)));
+        assert!(!config.menu.items[0].is_selectable());
+        assert!(matches!(config.menu.items[1], MenuEntry::Toggle(_)));
+        assert!(config.menu.items[1].is_selectable());
+        assert_eq!(config.menu.items[2].label(), "Aspect Ratio");
+        assert!(matches!(config.menu.items[3], MenuEntry::Slider(_)));
+        assert!(matches!(config.menu.items[4], MenuEntry::Spacer(_)));
+        assert!(!config.menu.items[4].is_selectable());
+        assert!(matches!(config.menu.items[5], MenuEntry::Disabled(_)));
+        assert!(!config.menu.items[5].is_selectable());
+        assert!(matches!(config.menu.items[6], MenuEntry::Action(_)));
     }
 }
@@ -0,0 +1,135 @@
+//! D-Bus control interface — an alternative to the Unix socket for desktop
+//! tools and scripts, exposing the same command surface as typed methods
+//! (`MenuToggle`, `MenuUp`, `MenuDown`, `MenuSelect`, `MenuBack`, `Popup`)
+//! plus `AchievementUnlocked`/`MenuOpened`/`MenuClosed` signals, the way
+//! MPRIS exposes player control and status over the bus.
+//!
+//! Runs on its own thread owning a `dbus-crossroads` connection. Method
+//! calls map straight to `socket::SocketCommand` and relay to `main` over
+//! `tx`, mirroring how `socket::listen` feeds the same channel. Signals are
+//! pushed the other way: `main`'s event loop sends a `DbusSignal` on
+//! `sig_rx` whenever it processes an unlock or a menu open/close, and this
+//! thread emits it onto the bus the next time it wakes to poll the
+//! connection. Gated entirely behind `config::DbusConfig::enabled` — when
+//! off, the overlay is socket-only, same as before this module existed.
+
+use std::sync::mpsc::{Receiver, Sender};
+use std::time::Duration;
+
+use dbus::blocking::LocalConnection;
+use dbus::channel::{MatchingReceiver, Sender as _};
+use dbus::message::MatchRule;
+use dbus::Message;
+use dbus_crossroads::{Crossroads, IfaceBuilder};
+use log::{error, info};
+
+use crate::config::DbusConfig;
+use crate::socket::SocketCommand;
+
+const SERVICE_NAME: &str = "org.superkonna.Overlay";
+const OBJECT_PATH: &str = "/org/superkonna/Overlay";
+const IFACE_NAME: &str = "org.superkonna.Overlay";
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Signal `main`'s event loop asks this thread to emit next time it polls
+/// the connection.
+#[derive(Debug)]
+pub enum DbusSignal {
+    AchievementUnlocked { title: String, description: String },
+    MenuOpened,
+    MenuClosed,
+}
+
+/// Spawn the D-Bus service thread and return. The thread loops forever, alternating between
+/// polling the connection (dispatching method calls) and draining
+/// `sig_rx` (emitting signals). A bind/name-ownership failure is logged
+/// and the thread exits — the overlay stays fully controllable via the
+/// Unix socket either way.
+pub fn spawn(cfg: DbusConfig, tx: Sender<SocketCommand>, sig_rx: Receiver<DbusSignal>) {
+    std::thread::spawn(move || {
+        if let Err(e) = run(&cfg, tx, sig_rx) {
+            error!("D-Bus service error: {e}");
+        }
+    });
+}
+
+fn run(cfg: &DbusConfig, tx: Sender<SocketCommand>, sig_rx: Receiver<DbusSignal>) -> Result<(), String> {
+    let conn = if cfg.system_bus {
+        LocalConnection::new_system()
+    } else {
+        LocalConnection::new_session()
+    }.map_err(|e| format!("connect: {e}"))?;
+
+    conn.request_name(SERVICE_NAME, false, true, false)
+        .map_err(|e| format!("request name {SERVICE_NAME}: {e}"))?;
+    info!("D-Bus service registered as {SERVICE_NAME} on the {} bus", if cfg.system_bus { "system" } else { "session" });
+
+    let mut cr = Crossroads::new();
+    let iface_token = cr.register(IFACE_NAME, |b: &mut IfaceBuilder<()>| {
+        register_method(b, "MenuToggle", &tx, |_| SocketCommand::MenuToggle);
+        register_method(b, "MenuUp", &tx, |_| SocketCommand::MenuUp);
+        register_method(b, "MenuDown", &tx, |_| SocketCommand::MenuDown);
+        register_method(b, "MenuSelect", &tx, |_| SocketCommand::MenuSelect);
+        register_method(b, "MenuBack", &tx, |_| SocketCommand::MenuBack);
+
+        let popup_tx = tx.clone();
+        b.method("Popup", ("title", "description"), (), move |_, _, (title, description): (String, String)| {
+            let _ = popup_tx.send(SocketCommand::Popup {
+                title,
+                description,
+                badge_path: None,
+                duration_ms: None,
+                priority: 0,
+            });
+            Ok(())
+        });
+    });
+    cr.insert(OBJECT_PATH, &[iface_token], ());
+
+    // `Crossroads` only dispatches when fed messages; wiring it up as the
+    // connection's receive handler lets `conn.process` below both service
+    // incoming method calls and return promptly so we can drain `sig_rx`.
+    conn.start_receive(MatchRule::new_method_call(), Box::new(move |msg, conn| {
+        cr.handle_message(msg, conn).is_ok()
+    }));
+
+    loop {
+        conn.process(POLL_INTERVAL).map_err(|e| format!("process: {e}"))?;
+        while let Ok(sig) = sig_rx.try_recv() {
+            emit_signal(&conn, sig);
+        }
+    }
+}
+
+/// Register a zero-argument, zero-return method that maps straight to a
+/// fixed `SocketCommand` — covers every bus method except `Popup`, which
+/// carries its own arguments.
+fn register_method(
+    b: &mut IfaceBuilder<()>,
+    name: &'static str,
+    tx: &Sender<SocketCommand>,
+    cmd: impl Fn(()) -> SocketCommand + Send + 'static,
+) {
+    let tx = tx.clone();
+    b.method(name, (), (), move |_, _, ()| {
+        let _ = tx.send(cmd(()));
+        Ok(())
+    });
+}
+
+fn emit_signal(conn: &LocalConnection, sig: DbusSignal) {
+    let msg = match sig {
+        DbusSignal::AchievementUnlocked { title, description } => {
+            Message::new_signal(OBJECT_PATH, IFACE_NAME, "AchievementUnlocked")
+                .map(|m| m.append2(title, description))
+        }
+        DbusSignal::MenuOpened => Message::new_signal(OBJECT_PATH, IFACE_NAME, "MenuOpened"),
+        DbusSignal::MenuClosed => Message::new_signal(OBJECT_PATH, IFACE_NAME, "MenuClosed"),
+    };
+    match msg {
+        Ok(msg) => {
+            let _ = conn.send(msg);
+        }
+        Err(e) => error!("Failed to build D-Bus signal: {e}"),
+    }
+}
@@ -0,0 +1,221 @@
+//! Dynamically-grown glyph atlas using a shelf (skyline) packer.
+//!
+//! Glyphs are expensive to rasterize but cheap to reuse — the same toast
+//! header, menu label, or clock digit is redrawn every frame. `GlyphAtlas`
+//! rasterizes each `(char, px_size)` once with `fontdue` and keeps the
+//! coverage bitmap packed into a single growable atlas, so steady-state
+//! rendering only ever looks glyphs up.
+//!
+//! Packing uses shelves: a list of horizontal strips, each with a fixed
+//! height and a cursor tracking how much of its width is used. A new glyph
+//! goes on the first shelf tall enough to hold it with room left, or a new
+//! shelf is opened at the bottom (growing the atlas) if none fits.
+//!
+//! The glyph lookup is bounded by a simple LRU (see `MAX_CACHED_GLYPHS`);
+//! eviction repacks the atlas so the backing pixel storage shrinks along
+//! with it, keeping memory stable even when a lot of distinct dynamic text
+//! (game names, search queries) churns through the cache.
+
+use std::collections::{HashMap, VecDeque};
+
+/// Fixed atlas width; height grows as shelves are added.
+const ATLAS_WIDTH: usize = 512;
+/// Padding between glyphs so bilinear sampling (if ever added) can't bleed.
+const GLYPH_PAD: usize = 1;
+/// Max distinct (char, size) glyphs kept in the rasterization cache. A theme
+/// only ever draws a few hundred distinct glyphs (digits, ASCII, a handful
+/// of symbols) at a handful of sizes, so this is a generous ceiling that
+/// mostly guards against unbounded growth from dynamic text (game names,
+/// search queries) rendered across many scaled sizes.
+const MAX_CACHED_GLYPHS: usize = 1000;
+
+/// Where a rasterized glyph lives in the atlas, plus the metrics needed to
+/// position and advance past it.
+#[derive(Clone, Copy)]
+pub struct GlyphInfo {
+    atlas_x: u32,
+    atlas_y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub xmin: i32,
+    pub ymin: i32,
+    pub advance: f32,
+}
+
+struct Shelf {
+    y: usize,
+    height: usize,
+    cursor_x: usize,
+}
+
+pub struct GlyphAtlas {
+    width: usize,
+    height: usize,
+    coverage: Vec<u8>,
+    shelves: Vec<Shelf>,
+    glyphs: HashMap<(char, u32), GlyphInfo>,
+    /// Most- to least-recently-used order of `glyphs` keys, for LRU eviction.
+    /// Dropping a key also triggers `compact()`, which repacks every
+    /// surviving glyph into a freshly-sized atlas — so the backing pixel
+    /// storage actually shrinks under churny dynamic text, not just the
+    /// lookup map.
+    recency: VecDeque<(char, u32)>,
+    advances: HashMap<(char, u32), f32>,
+}
+
+impl GlyphAtlas {
+    pub fn new() -> Self {
+        GlyphAtlas {
+            width: ATLAS_WIDTH,
+            height: 0,
+            coverage: Vec::new(),
+            shelves: Vec::new(),
+            glyphs: HashMap::new(),
+            recency: VecDeque::new(),
+            advances: HashMap::new(),
+        }
+    }
+
+    /// Advance width of `ch` at `px_size`, cached separately from the full
+    /// glyph raster so `measure_text`/`truncate_to_width` (layout-only,
+    /// never drawn) don't pay for rasterizing and packing a bitmap.
+    pub fn advance(&mut self, font: &fontdue::Font, ch: char, px_size: f32) -> f32 {
+        let key = (ch, px_size.to_bits());
+        if let Some(info) = self.glyphs.get(&key) {
+            return info.advance;
+        }
+        *self.advances.entry(key).or_insert_with(|| font.metrics(ch, px_size).advance_width)
+    }
+
+    /// Get the cached glyph for `ch` at `px_size`, rasterizing and packing
+    /// it into the atlas on first use.
+    pub fn glyph(&mut self, font: &fontdue::Font, ch: char, px_size: f32) -> GlyphInfo {
+        let key = (ch, px_size.to_bits());
+        if let Some(info) = self.glyphs.get(&key) {
+            let info = *info;
+            self.touch(key);
+            return info;
+        }
+
+        let (metrics, bitmap) = font.rasterize(ch, px_size);
+        let (w, h) = (metrics.width, metrics.height);
+        let shelf_idx = self.shelf_for(w, h);
+        let shelf = &mut self.shelves[shelf_idx];
+        let (atlas_x, atlas_y) = (shelf.cursor_x, shelf.y);
+        shelf.cursor_x += w + GLYPH_PAD;
+
+        for row in 0..h {
+            let dst = (atlas_y + row) * self.width + atlas_x;
+            let src = row * w;
+            self.coverage[dst..dst + w].copy_from_slice(&bitmap[src..src + w]);
+        }
+
+        let info = GlyphInfo {
+            atlas_x: atlas_x as u32,
+            atlas_y: atlas_y as u32,
+            width: w as u32,
+            height: h as u32,
+            xmin: metrics.xmin,
+            ymin: metrics.ymin,
+            advance: metrics.advance_width,
+        };
+        self.glyphs.insert(key, info);
+        self.touch(key);
+        self.evict_stale();
+        // `evict_stale` may have repacked the atlas, moving this glyph.
+        self.glyphs[&key]
+    }
+
+    /// Mark `key` as most-recently-used.
+    fn touch(&mut self, key: (char, u32)) {
+        self.recency.retain(|k| *k != key);
+        self.recency.push_back(key);
+    }
+
+    /// Drop the least-recently-used glyph lookups until back under
+    /// `MAX_CACHED_GLYPHS`, then repack the atlas so their pixel storage is
+    /// actually reclaimed rather than just their lookup entry.
+    fn evict_stale(&mut self) {
+        let mut evicted = false;
+        while self.glyphs.len() > MAX_CACHED_GLYPHS {
+            let Some(oldest) = self.recency.pop_front() else { break };
+            if self.glyphs.remove(&oldest).is_some() {
+                evicted = true;
+            }
+        }
+        if evicted {
+            self.compact();
+        }
+    }
+
+    /// Repack every still-cached glyph into a freshly-sized atlas. Without
+    /// this, `coverage`/`shelves` only ever grow — `evict_stale` would bound
+    /// the lookup map but not the atlas's dominant memory cost, the pixel
+    /// data itself.
+    fn compact(&mut self) {
+        let mut entries: Vec<((char, u32), GlyphInfo, Vec<u8>)> = self.glyphs.iter()
+            .map(|(&key, &info)| {
+                let (w, h) = (info.width as usize, info.height as usize);
+                let mut pixels = vec![0u8; w * h];
+                for row in 0..h {
+                    let src = (info.atlas_y as usize + row) * self.width + info.atlas_x as usize;
+                    let dst = row * w;
+                    pixels[dst..dst + w].copy_from_slice(&self.coverage[src..src + w]);
+                }
+                (key, info, pixels)
+            })
+            .collect();
+        // Tallest glyphs first packs more densely onto fewer shelves.
+        entries.sort_by(|a, b| b.1.height.cmp(&a.1.height));
+
+        self.height = 0;
+        self.coverage.clear();
+        self.shelves.clear();
+
+        for (key, mut info, pixels) in entries {
+            let (w, h) = (info.width as usize, info.height as usize);
+            let shelf_idx = self.shelf_for(w, h);
+            let shelf = &mut self.shelves[shelf_idx];
+            let (atlas_x, atlas_y) = (shelf.cursor_x, shelf.y);
+            shelf.cursor_x += w + GLYPH_PAD;
+
+            for row in 0..h {
+                let dst = (atlas_y + row) * self.width + atlas_x;
+                let src = row * w;
+                self.coverage[dst..dst + w].copy_from_slice(&pixels[src..src + w]);
+            }
+
+            info.atlas_x = atlas_x as u32;
+            info.atlas_y = atlas_y as u32;
+            self.glyphs.insert(key, info);
+        }
+    }
+
+    /// Coverage (0-255) at `(row, col)` within a previously-returned glyph.
+    pub fn coverage(&self, info: &GlyphInfo, row: usize, col: usize) -> u8 {
+        self.coverage[(info.atlas_y as usize + row) * self.width + info.atlas_x as usize + col]
+    }
+
+    /// Find a shelf with enough height and remaining width, else open a new
+    /// one at the bottom, growing the backing bitmap to fit it.
+    fn shelf_for(&mut self, w: usize, h: usize) -> usize {
+        for (i, shelf) in self.shelves.iter().enumerate() {
+            if shelf.height >= h && shelf.cursor_x + w <= self.width {
+                return i;
+            }
+        }
+
+        let y = self.height;
+        let shelf_height = h + GLYPH_PAD;
+        self.height += shelf_height;
+        self.coverage.resize(self.width * self.height, 0);
+        self.shelves.push(Shelf { y, height: shelf_height, cursor_x: 0 });
+        self.shelves.len() - 1
+    }
+}
+
+impl Default for GlyphAtlas {
+    fn default() -> Self {
+        GlyphAtlas::new()
+    }
+}
@@ -0,0 +1,289 @@
+//! Gamepad input — the hardware producer for `bindings::Bindings`' query API.
+//!
+//! Polls a gamepad via `gilrs` on its own thread and runs a per-[`Button`]
+//! press/hold/confirm state machine: a button-down starts an independent
+//! timer; releasing it before the bound hold action's `hold_ms` emits the
+//! press action it triggers, while staying down past that threshold emits
+//! its hold action exactly once and suppresses the subsequent release (so
+//! a button bound to both a press and a hold action never fires twice).
+//! Like `press_action_for`/`hold_action_for`, both are resolved against the
+//! full held-button set, not just the one button that changed, so a chord
+//! binding (e.g. `l1+a`) fires only while its modifiers are also down; each
+//! button only resolves actions it is the trigger of, so two buttons held
+//! at once never compete for one action. Actions marked `confirm` don't fire on
+//! their own — they arm a pending confirmation that a later press of the
+//! `confirm` action finalizes, or that a `back` press cancels.
+//!
+//! The state machine ([`InputState`]) is hardware-agnostic and kept separate
+//! from the `gilrs` polling loop so it can be exercised directly in tests.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use gilrs::{EventType, Gilrs};
+use log::{error, info};
+
+use superkonna_overlay::bindings::{Action, Bindings};
+use superkonna_overlay::buttons::Button;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Per-button press/hold/confirm state machine, driven by raw down/up
+/// events and a periodic [`InputState::tick`] to catch hold thresholds.
+pub struct InputState {
+    bindings: Bindings,
+    down_since: HashMap<Button, Instant>,
+    /// Buttons whose hold action has already fired while still held, so the
+    /// eventual release doesn't also emit a press.
+    hold_fired: HashSet<Button>,
+    /// A `confirm == true` action waiting for a second press of `confirm`
+    /// (or a cancel via `back`) before it actually fires.
+    pending_confirm: Option<Action>,
+}
+
+impl InputState {
+    pub fn new(bindings: Bindings) -> Self {
+        InputState {
+            bindings,
+            down_since: HashMap::new(),
+            hold_fired: HashSet::new(),
+            pending_confirm: None,
+        }
+    }
+
+    /// Call when a button goes down. Starts (or ignores an already-running)
+    /// independent timer for this button.
+    pub fn button_down(&mut self, button: Button) {
+        self.down_since.entry(button).or_insert_with(Instant::now);
+    }
+
+    /// Call when a button goes up. Returns the action that fired, if any.
+    pub fn button_up(&mut self, button: Button) -> Option<Action> {
+        // Capture the held set (including `button` itself — it's still in
+        // `down_since` here) so a chord's modifiers can be checked, then
+        // drop the button's own timer.
+        let held: Vec<Button> = self.down_since.keys().copied().collect();
+        self.down_since.remove(&button);
+
+        // The hold action already fired while this button was held — the
+        // release itself is not a separate press.
+        if self.hold_fired.remove(&button) {
+            return None;
+        }
+
+        let action = self.action_for(button, &held, false)?.clone();
+        self.fire(action)
+    }
+
+    /// The press (`hold == false`) or hold action triggered by `button`
+    /// whose chord is satisfied by `held`, preferring the most specific
+    /// (most modifiers) match, as `Bindings::press_action_for` does.
+    fn action_for(&self, button: Button, held: &[Button], hold: bool) -> Option<&Action> {
+        self.bindings.actions.values()
+            .filter(|a| a.hold == hold && a.chord.trigger == button && a.chord.matches(held))
+            .max_by_key(|a| a.chord.modifiers.len())
+    }
+
+    /// Call periodically to promote held buttons past their hold threshold.
+    /// Returns every hold action that fired this tick.
+    pub fn tick(&mut self) -> Vec<Action> {
+        let held: Vec<Button> = self.down_since.keys().copied().collect();
+
+        let crossed: Vec<(Button, Action)> = self.down_since.iter()
+            .filter_map(|(&button, start)| {
+                if self.hold_fired.contains(&button) {
+                    return None;
+                }
+                // Only the trigger's own timer counts — a modifier that's
+                // also in `down_since` never fires the chord a second time
+                // off its own (usually earlier) start instant.
+                let action = self.action_for(button, &held, true)?;
+                (start.elapsed().as_millis() as u64 >= action.hold_ms)
+                    .then(|| (button, action.clone()))
+            })
+            .collect();
+
+        crossed.into_iter()
+            .filter_map(|(button, action)| {
+                self.hold_fired.insert(button);
+                self.fire(action)
+            })
+            .collect()
+    }
+
+    /// Hold progress in `[0.0, 1.0]` for a button's bound hold action, for
+    /// the hint bar to animate fill. `0.0` if the button isn't down, isn't
+    /// a hold action's trigger, or has no hold action bound (including a
+    /// chord whose modifiers aren't currently all held).
+    pub fn hold_progress(&self, button: Button) -> f32 {
+        let held: Vec<Button> = self.down_since.keys().copied().collect();
+        let Some(action) = self.action_for(button, &held, true) else { return 0.0 };
+        let Some(start) = self.down_since.get(&button) else { return 0.0 };
+        (start.elapsed().as_millis() as f32 / action.hold_ms as f32).min(1.0)
+    }
+
+    /// Resolve confirm/cancel semantics for an action that's ready to fire,
+    /// returning the action to actually emit (if any).
+    fn fire(&mut self, action: Action) -> Option<Action> {
+        if let Some(pending) = &self.pending_confirm {
+            if action.name == "confirm" && pending.name != "confirm" {
+                return self.pending_confirm.take();
+            }
+            if action.name == "back" {
+                self.pending_confirm = None;
+                // Fall through — `back` still fires normally.
+            }
+        }
+
+        if action.confirm {
+            self.pending_confirm = Some(action);
+            None
+        } else {
+            Some(action)
+        }
+    }
+}
+
+/// Spawn the gamepad polling thread and return; the thread polls forever. A missing/unsupported
+/// gamepad backend is logged and the thread exits — the overlay stays fully
+/// controllable via the socket and D-Bus interfaces either way.
+pub fn spawn(bindings: Bindings, tx: Sender<Action>) {
+    std::thread::spawn(move || {
+        if let Err(e) = run(bindings, tx) {
+            error!("Gamepad input error: {e}");
+        }
+    });
+}
+
+fn run(bindings: Bindings, tx: Sender<Action>) -> Result<(), String> {
+    let mut gilrs = Gilrs::new().map_err(|e| format!("gilrs init: {e}"))?;
+    let mut state = InputState::new(bindings);
+    info!("Gamepad input polling started");
+
+    loop {
+        while let Some(event) = gilrs.next_event() {
+            match event.event {
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(button) = map_button(button) {
+                        state.button_down(button);
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(button) = map_button(button) {
+                        if let Some(action) = state.button_up(button) {
+                            let _ = tx.send(action);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for action in state.tick() {
+            let _ = tx.send(action);
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Map a `gilrs` button code to our abstract [`Button`] (SDL/ES convention).
+fn map_button(button: gilrs::Button) -> Option<Button> {
+    use gilrs::Button as G;
+    match button {
+        G::South => Some(Button::A),
+        G::East => Some(Button::B),
+        G::West => Some(Button::X),
+        G::North => Some(Button::Y),
+        G::LeftTrigger => Some(Button::LB),
+        G::RightTrigger => Some(Button::RB),
+        G::LeftTrigger2 => Some(Button::LT),
+        G::RightTrigger2 => Some(Button::RT),
+        G::Start => Some(Button::Start),
+        G::Select => Some(Button::Select),
+        G::DPadUp => Some(Button::DpadUp),
+        G::DPadDown => Some(Button::DpadDown),
+        G::DPadLeft => Some(Button::DpadLeft),
+        G::DPadRight => Some(Button::DpadRight),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    fn test_bindings() -> Bindings {
+        Bindings::builtin_default()
+    }
+
+    #[test]
+    fn press_before_hold_threshold_emits_press() {
+        let mut state = InputState::new(test_bindings());
+        // `resume` binds `b` as a quick press (no hold).
+        state.button_down(Button::B);
+        let action = state.button_up(Button::B).expect("press action");
+        assert_eq!(action.name, "resume");
+    }
+
+    #[test]
+    fn hold_past_threshold_emits_hold_and_suppresses_release() {
+        let mut state = InputState::new(test_bindings());
+        // `save_state` binds `y` as hold (1500ms).
+        state.button_down(Button::Y);
+        assert!(state.tick().is_empty());
+
+        sleep(Duration::from_millis(10));
+        assert!(state.hold_progress(Button::Y) > 0.0);
+
+        // Simulate the threshold having passed without sleeping 1.5s in a test.
+        state.down_since.insert(Button::Y, Instant::now() - Duration::from_millis(1600));
+        let fired = state.tick();
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].name, "save_state");
+
+        // Release after the hold fired must not also emit a press.
+        assert!(state.button_up(Button::Y).is_none());
+    }
+
+    #[test]
+    fn confirm_action_waits_for_second_confirm_press() {
+        let mut state = InputState::new(test_bindings());
+        // `quit_to_es` binds `start` as a hold+confirm action (2000ms).
+        state.down_since.insert(Button::Start, Instant::now() - Duration::from_millis(2100));
+        let fired = state.tick();
+        assert!(fired.is_empty(), "confirm-gated action must not fire on first completion");
+
+        // Second press of the confirm action (`a`) finalizes it.
+        state.button_down(Button::A);
+        let action = state.button_up(Button::A).expect("confirmed action");
+        assert_eq!(action.name, "quit_to_es");
+    }
+
+    #[test]
+    fn back_cancels_pending_confirm() {
+        let mut state = InputState::new(test_bindings());
+        state.down_since.insert(Button::Start, Instant::now() - Duration::from_millis(2100));
+        state.tick();
+        assert!(state.pending_confirm.is_some());
+
+        // `back` binds `select` in the built-in bindings.
+        state.button_down(Button::Select);
+        state.button_up(Button::Select);
+        assert!(state.pending_confirm.is_none());
+    }
+
+    #[test]
+    fn simultaneous_presses_track_independent_timers() {
+        let mut state = InputState::new(test_bindings());
+        state.button_down(Button::Y);
+        sleep(Duration::from_millis(5));
+        state.button_down(Button::Start);
+
+        assert!(state.down_since.contains_key(&Button::Y));
+        assert!(state.down_since.contains_key(&Button::Start));
+        assert!(state.hold_progress(Button::Start) < state.hold_progress(Button::Y));
+    }
+}
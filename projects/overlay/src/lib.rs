@@ -1,8 +1,16 @@
 //! Shared modules for the overlay crate.
-//! Used by the main binary (X11) and the preview binary (cross-platform).
+//! Used by the main binary (X11 or Wayland, picked at runtime) and the
+//! preview binary (cross-platform, renders to PNG instead of a window).
 
+mod bidi;
+pub mod bindings;
+pub mod buttons;
 pub mod config;
+mod glyph_cache;
 pub mod menu;
 pub mod popup;
 pub mod renderer;
+pub mod retroarch;
+pub mod sound;
 pub mod theme;
+pub mod tracker;
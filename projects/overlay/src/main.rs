@@ -1,20 +1,31 @@
-//! superkonna-overlay: Themed achievement popup + ingame menu overlay for Batocera (X11)
+//! superkonna-overlay: Themed achievement popup + ingame menu overlay for Batocera
 //!
 //! Monitors RetroArch's log file for RetroAchievements events, listens on a
 //! Unix socket for menu commands, and renders themed popups and an ingame menu
-//! using an X11 override-redirect window.
+//! on top of the game. Presentation goes through [`surface::OverlaySurface`],
+//! which picks an X11 override-redirect window or a Wayland `wlr-layer-shell`
+//! surface depending on the session.
 
-mod audio;
+mod dbus;
+mod input;
 mod popup;
-mod retroarch;
+mod ra_api;
 mod socket;
+mod surface;
 mod watcher;
+mod wayland;
 mod window;
 
+use surface::OverlaySurface;
+
+use superkonna_overlay::bindings::{self, Bindings};
 use superkonna_overlay::config;
 use superkonna_overlay::menu;
 use superkonna_overlay::renderer;
+use superkonna_overlay::retroarch;
+use superkonna_overlay::sound::SoundPlayer;
 use superkonna_overlay::theme;
+use superkonna_overlay::tracker::TrackerSet;
 
 use std::path::PathBuf;
 use std::sync::mpsc;
@@ -61,36 +72,42 @@ fn main() {
         .and_then(|s| s.parse().ok())
         .unwrap_or(1080);
 
-    let mut win = match window::OverlayWindow::new(init_w, init_h) {
+    let mut win = match surface::create_surface(init_w, init_h) {
         Ok(w) => w,
         Err(e) => {
-            error!("Failed to create X11 window: {e}");
+            error!("Failed to create overlay surface: {e}");
             std::process::exit(1);
         }
     };
     win.hide();
+    win.set_click_through(true);
     let (screen_w, screen_h) = win.screen_size();
-    info!("X11 overlay window created ({}x{})", screen_w, screen_h);
+    info!("Overlay surface created ({}x{})", screen_w, screen_h);
 
     // Create renderer
     let rend = renderer::Renderer::new(&theme);
 
-    // Create RetroArch client
-    let ra_client = retroarch::RetroArchClient::new(
-        &overlay_config.menu.retroarch.host,
-        overlay_config.menu.retroarch.port,
-    )
-    .ok();
-    if ra_client.is_some() {
-        info!("RetroArch UDP client ready");
-    } else {
-        warn!("Could not create RetroArch UDP client");
-    }
-
     // Unified event channel
     enum Event {
         Achievement(watcher::AchievementEvent),
         Socket(socket::SocketCommand),
+        /// A badge image finished fetching for the unlock popup tagged
+        /// with this achievement id (see `ra_api`).
+        Badge(String, Vec<u8>),
+        /// A semantic action fired by the gamepad input state machine (see
+        /// `input`). Effect wiring (mapping action names to menu/overlay
+        /// behavior) lands in a later chunk — for now this just proves the
+        /// `Bindings` query API has a real hardware producer.
+        Input(bindings::Action),
+        /// A freshly-polled `GET_STATUS` reply from RetroArch (see
+        /// `retroarch::spawn_status_poll`).
+        RetroArchStatus(retroarch::RunState),
+        /// `bindings.toml` was reloaded on disk (see `Bindings::watch`).
+        /// Rebuilds `game_menu`'s item list from the new bindings. The hint
+        /// bar is unaffected: `renderer::render_menu` draws a hardcoded
+        /// A/Select, B/Back pair rather than reading `Bindings`' hint data,
+        /// so there's nothing live to rebuild there yet.
+        BindingsReloaded(Bindings),
     }
 
     let (tx, rx) = mpsc::channel::<Event>();
@@ -135,15 +152,120 @@ fn main() {
     }
     info!("Socket listener started at {SOCKET_PATH}");
 
+    // Optional D-Bus control interface — same command surface as the
+    // socket, exposed as typed methods/signals for desktop tools.
+    let dbus_sig_tx: Option<mpsc::Sender<dbus::DbusSignal>> = if overlay_config.dbus.enabled {
+        let (dtx, drx) = mpsc::channel();
+        let (sig_tx, sig_rx) = mpsc::channel();
+        dbus::spawn(overlay_config.dbus.clone(), dtx, sig_rx);
+
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            for cmd in drx {
+                if tx.send(Event::Socket(cmd)).is_err() {
+                    break;
+                }
+            }
+        });
+        info!("D-Bus control interface enabled");
+        Some(sig_tx)
+    } else {
+        None
+    };
+
+    // Gamepad input — resolves raw button presses/holds into the semantic
+    // actions defined in bindings.toml via its own state machine.
+    {
+        let reload_tx = tx.clone();
+        let bindings_handle = Bindings::watch(&theme_root, move |b| {
+            info!("bindings.toml reloaded: {} actions, {} menu items", b.actions.len(), b.menu_items.len());
+            let _ = reload_tx.send(Event::BindingsReloaded(b.clone()));
+        });
+        let (itx, irx) = mpsc::channel();
+        input::spawn(bindings_handle.current(), itx);
+
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            for action in irx {
+                if tx.send(Event::Input(action)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    info!("Gamepad input enabled");
+
+    // RetroArch status polling — keeps the menu header's game name in sync
+    // with whatever core/content RetroArch actually has loaded, independent
+    // of the socket's explicit SetGameName command.
+    {
+        let (rtx, rrx) = mpsc::channel();
+        retroarch::spawn_status_poll(
+            overlay_config.menu.retroarch.host.clone(),
+            overlay_config.menu.retroarch.port,
+            rtx,
+        );
+
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            for state in rrx {
+                if tx.send(Event::RetroArchStatus(state)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    info!("RetroArch status polling started");
+
     // State
-    let mut popup_queue = popup::PopupQueue::new();
+    let mut popup_queue = popup::PopupQueue::with_max_visible(overlay_config.toasts.max_visible);
     let mut game_menu = menu::Menu::new(overlay_config.menu.items.clone());
+    game_menu.set_retroarch_endpoint(
+        overlay_config.menu.retroarch.host.clone(),
+        overlay_config.menu.retroarch.port,
+    );
     let menu_config = overlay_config.menu.clone();
+    let mut game_name: Option<String> = None;
+    let mut trackers = TrackerSet::new();
+    let mut menu_was_visible = false;
     let frame_duration = Duration::from_millis(16); // ~60fps
 
     // Sound paths
     let sounds_dir = theme_root.join("assets").join("sounds");
 
+    // Mixer-based UI cues (toast chime, menu move/select) — best-effort
+    let sound_player = SoundPlayer::new(&overlay_config.sounds, &sounds_dir);
+    if sound_player.is_some() {
+        info!("Sound mixer ready");
+    } else {
+        warn!("Sound cues unavailable (disabled or no output device)");
+    }
+
+    // Optional direct RetroAchievements Web API data source — when
+    // configured, fetches badge icons and attaches them to the queued
+    // unlock popups. The popup this loop draws (`Renderer::render_popup`)
+    // shows title and description only; the badge is not drawn there.
+    let badge_id_tx: Option<mpsc::Sender<String>> = if overlay_config.retroachievements.is_configured() {
+        let (id_tx, id_rx) = mpsc::channel::<String>();
+        let (meta_tx, meta_rx) = mpsc::channel();
+        let cache_dir = theme_root.join("assets").join("cache").join("badges");
+        ra_api::spawn(overlay_config.retroachievements.clone(), cache_dir, id_rx, meta_tx);
+
+        let relay_tx = tx.clone();
+        std::thread::spawn(move || {
+            for (id, badge_png) in meta_rx {
+                if relay_tx.send(Event::Badge(id, badge_png)).is_err() {
+                    break;
+                }
+            }
+        });
+        info!("RetroAchievements API badge fetching enabled");
+        Some(id_tx)
+    } else {
+        debug!("RetroAchievements API not configured — unlock popups stay text-only");
+        None
+    };
+
     loop {
         let frame_start = Instant::now();
 
@@ -151,102 +273,232 @@ fn main() {
         while let Ok(event) = rx.try_recv() {
             debug!("Main loop received event");
             match event {
-                Event::Achievement(ach) => {
-                    info!("Achievement: {} — {}", ach.title, ach.description);
-                    popup_queue.push(popup::Popup::new(ach.title, ach.description));
-                    // Play achievement sound
-                    audio::play_sound(&sounds_dir.join("achievement.wav"));
-                }
+                Event::Achievement(event) => match event {
+                    watcher::AchievementEvent::Unlock { id, title, description, .. } => {
+                        info!("Achievement unlocked: {title} — {description}");
+                        if let Some(ref sig_tx) = dbus_sig_tx {
+                            let _ = sig_tx.send(dbus::DbusSignal::AchievementUnlocked {
+                                title: title.clone(),
+                                description: description.clone(),
+                            });
+                        }
+                        popup_queue.push(popup::Popup::new(title, description).with_id(id.clone()));
+                        if let Some(ref player) = sound_player {
+                            player.play_toast();
+                        }
+                        if let Some(ref id_tx) = badge_id_tx {
+                            let _ = id_tx.send(id);
+                        }
+                    }
+                    watcher::AchievementEvent::Mastery { game, hardcore } => {
+                        info!("Mastery: {game} (hardcore={hardcore})");
+                        let title = if hardcore { "Mastered" } else { "Completed" }.to_string();
+                        popup_queue.push(popup::Popup::new(title, game));
+                        if let Some(ref player) = sound_player {
+                            player.play_toast();
+                        }
+                    }
+                    watcher::AchievementEvent::LeaderboardStarted { id, name } => {
+                        debug!("Leaderboard started: {name}");
+                        trackers.start(id, name);
+                    }
+                    watcher::AchievementEvent::LeaderboardUpdated { id, value } => {
+                        trackers.update_value(&id, value);
+                    }
+                    watcher::AchievementEvent::LeaderboardSubmitted { id, name, value } => {
+                        info!("Leaderboard submitted: {name} = {value}");
+                        trackers.remove(&id);
+                        popup_queue.push(popup::Popup::new(name, value));
+                    }
+                    watcher::AchievementEvent::LeaderboardCanceled { id, name } => {
+                        debug!("Leaderboard canceled: {name}");
+                        trackers.remove(&id);
+                    }
+                    watcher::AchievementEvent::ChallengeShown { id } => {
+                        debug!("Challenge shown: {id}");
+                    }
+                    watcher::AchievementEvent::ChallengeHidden { id } => {
+                        debug!("Challenge hidden: {id}");
+                    }
+                    watcher::AchievementEvent::LoginSucceeded => {
+                        info!("RetroAchievements login succeeded");
+                    }
+                    watcher::AchievementEvent::ProgressIndicator { id, current, target } => {
+                        debug!("Progress indicator {id}: {current}/{target}");
+                    }
+                },
                 Event::Socket(cmd) => match cmd {
                     socket::SocketCommand::MenuToggle => {
                         game_menu.toggle();
                         if game_menu.is_visible() {
-                            if let Some(snd) = &menu_config.sound_select {
-                                audio::play_sound(&sounds_dir.join(snd));
+                            if let Some(ref player) = sound_player {
+                                player.play_menu_select();
                             }
                         }
                     }
                     socket::SocketCommand::MenuUp => {
                         game_menu.move_up();
-                        if let Some(snd) = &menu_config.sound_scroll {
-                            audio::play_sound(&sounds_dir.join(snd));
+                        if let Some(ref player) = sound_player {
+                            player.play_menu_move();
                         }
                     }
                     socket::SocketCommand::MenuDown => {
                         game_menu.move_down();
-                        if let Some(snd) = &menu_config.sound_scroll {
-                            audio::play_sound(&sounds_dir.join(snd));
+                        if let Some(ref player) = sound_player {
+                            player.play_menu_move();
                         }
                     }
                     socket::SocketCommand::MenuSelect => {
+                        // `Menu::select` dispatches `shell`/`retroarch` actions itself
+                        // (on a worker thread, tracked via `Executing`/`Success`/`Error`);
+                        // the returned action here is only used for the select sound cue.
                         if let Some(action) = game_menu.select() {
-                            if let Some(snd) = &menu_config.sound_select {
-                                audio::play_sound(&sounds_dir.join(snd));
+                            if let Some(ref player) = sound_player {
+                                player.play_menu_select();
                             }
-                            match action {
-                                menu::MenuAction::Dismiss => {}
-                                menu::MenuAction::RetroArch(cmd) => {
-                                    if let Some(ref client) = ra_client {
-                                        client.send_command(&cmd);
-                                    }
-                                }
-                                menu::MenuAction::Shell(cmd) => {
-                                    info!("Executing shell: {cmd}");
-                                    let _ = std::process::Command::new("sh")
-                                        .args(["-c", &cmd])
-                                        .spawn();
-                                }
+                            if let menu::MenuAction::Shell(ref cmd) = action {
+                                info!("Executing shell: {cmd}");
                             }
                         }
                     }
                     socket::SocketCommand::MenuBack => {
                         game_menu.back();
-                        if let Some(snd) = &menu_config.sound_back {
-                            audio::play_sound(&sounds_dir.join(snd));
+                        if let Some(ref player) = sound_player {
+                            player.play_menu_back();
                         }
                     }
-                    socket::SocketCommand::Popup { title, description } => {
+                    socket::SocketCommand::MenuSearchChar(ch) => {
+                        game_menu.push_char(ch);
+                    }
+                    socket::SocketCommand::MenuSearchBackspace => {
+                        game_menu.backspace();
+                    }
+                    socket::SocketCommand::MenuSearchCaretLeft => {
+                        game_menu.move_caret_left();
+                    }
+                    socket::SocketCommand::MenuSearchCaretRight => {
+                        game_menu.move_caret_right();
+                    }
+                    socket::SocketCommand::Popup { title, description, badge_path, duration_ms, priority } => {
                         info!("Popup received via socket: {title} | {description}");
-                        popup_queue.push(popup::Popup::new(title, description));
+                        let mut pop = popup::Popup::new(title, description).with_priority(priority);
+                        if let Some(ms) = duration_ms {
+                            pop = pop.with_hold_ms(ms as u64);
+                        }
+                        if let Some(path) = badge_path {
+                            match std::fs::read(&path) {
+                                Ok(bytes) => pop = pop.with_badge(bytes),
+                                Err(e) => warn!("Failed to read badge {}: {e}", path.display()),
+                            }
+                        }
+                        popup_queue.push(pop);
+                        if let Some(ref player) = sound_player {
+                            player.play_toast();
+                        }
+                    }
+                    socket::SocketCommand::MenuSetItems(items) => {
+                        info!("Menu items replaced via socket ({} items)", items.len());
+                        game_menu.set_items(items);
+                    }
+                    socket::SocketCommand::SetGameName(name) => {
+                        debug!("Game name updated via socket: {name}");
+                        game_name = Some(name);
+                    }
+                },
+                Event::Badge(id, badge_png) => {
+                    debug!("Badge fetched for achievement {id}");
+                    popup_queue.set_badge(&id, badge_png);
+                }
+                Event::Input(action) => {
+                    info!("Gamepad action fired: {} (button={})", action.name, action.button_name);
+                }
+                Event::RetroArchStatus(state) => {
+                    game_name = match state {
+                        retroarch::RunState::Playing(_, game) | retroarch::RunState::Paused(_, game) => Some(game),
+                        retroarch::RunState::Contentless => None,
+                    };
+                }
+                Event::BindingsReloaded(bindings) => match bindings.to_menu_items() {
+                    Ok(items) => {
+                        info!("Rebuilding menu from reloaded bindings ({} items)", items.len());
+                        game_menu.set_items(items.into_iter().map(config::MenuEntry::Action).collect());
                     }
+                    Err(e) => warn!("Reloaded bindings.toml produced an unusable menu item list: {e} — keeping previous menu"),
                 },
             }
         }
 
         // Tick animations
-        popup_queue.tick();
+        popup_queue.tick(frame_duration);
         game_menu.tick();
 
         // Determine what to render
         let has_popup = popup_queue.current().is_some();
         let has_menu = game_menu.is_visible();
+        let has_trackers = !trackers.is_empty();
+
+        if has_menu != menu_was_visible {
+            if let Some(ref sig_tx) = dbus_sig_tx {
+                let sig = if has_menu { dbus::DbusSignal::MenuOpened } else { dbus::DbusSignal::MenuClosed };
+                let _ = sig_tx.send(sig);
+            }
+            // Menu rows are interactive and need the whole window to accept
+            // clicks; with only a toast/tracker on screen the overlay should
+            // stay click-through so it doesn't steal input meant for the
+            // game behind it.
+            win.set_click_through(!has_menu);
+            menu_was_visible = has_menu;
+        }
 
         if has_menu {
-            let pixels = rend.render_menu(&game_menu, screen_w as u32, screen_h as u32, &menu_config);
+            let pixels = rend.render_menu(&game_menu, screen_w as u32, screen_h as u32, &menu_config, &overlay_config.text_fit, game_name.as_deref());
             win.show();
-            win.update_pixels(&pixels, screen_w, screen_h);
-        } else if has_popup {
-            let popup = popup_queue.current().unwrap();
-            let popup_pixels = rend.render_popup(&popup.title, &popup.description, popup.opacity());
+            win.present(&pixels);
+        } else if has_popup || has_trackers {
             let sw = screen_w as u32;
             let sh = screen_h as u32;
             let total = (sw * sh) as usize;
             let mut screen = vec![0u32; total];
-            let pw: u32 = 640;
-            let ph: u32 = 140;
-            let offset_x = sw.saturating_sub(pw + 20);
-            let offset_y = 20_u32;
-            for row in 0..ph {
-                for col in 0..pw {
-                    let src_idx = (row * pw + col) as usize;
-                    let dst_idx = ((offset_y + row) * sw + offset_x + col) as usize;
-                    if dst_idx < total {
-                        screen[dst_idx] = popup_pixels[src_idx];
+
+            if has_popup {
+                let popup = popup_queue.current().unwrap();
+                let popup_pixels = rend.render_popup(&popup.title, &popup.description, popup.opacity(), &overlay_config.text_fit, popup.elapsed_ms());
+                let pw: u32 = 640;
+                let ph: u32 = 140;
+                let offset_x = sw.saturating_sub(pw + 20);
+                let offset_y = 20_u32;
+                for row in 0..ph {
+                    for col in 0..pw {
+                        let src_idx = (row * pw + col) as usize;
+                        let dst_idx = ((offset_y + row) * sw + offset_x + col) as usize;
+                        if dst_idx < total {
+                            screen[dst_idx] = popup_pixels[src_idx];
+                        }
                     }
                 }
             }
+
+            // Tracker stack is independent of the popup queue and menu — it
+            // composites into the bottom-right corner whenever any attempt
+            // is active, regardless of what else is on screen.
+            if has_trackers {
+                let rows: Vec<(String, String)> = trackers.rows().map(|t| (t.name.clone(), t.value.clone())).collect();
+                let (tracker_pixels, tw, th) = rend.render_trackers(&rows);
+                let offset_x = sw.saturating_sub(tw + 20);
+                let offset_y = sh.saturating_sub(th + 20);
+                for row in 0..th {
+                    for col in 0..tw {
+                        let src_idx = (row * tw + col) as usize;
+                        let dst_idx = ((offset_y + row) * sw + offset_x + col) as usize;
+                        if dst_idx < total {
+                            screen[dst_idx] = tracker_pixels[src_idx];
+                        }
+                    }
+                }
+            }
+
             win.show();
-            win.update_pixels(&screen, screen_w, screen_h);
+            win.present(&screen);
         } else {
             win.hide();
         }
@@ -1,18 +1,35 @@
 //! Menu state machine with cursor navigation and confirm logic.
 
-use crate::config::MenuItem;
-use std::collections::HashMap;
+use crate::config::{ActionKind, MenuEntry, MenuItem};
+use crate::retroarch::RetroArchClient;
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
 use std::time::Instant;
 
 const OPEN_DURATION_MS: u64 = 200;
 const CLOSE_DURATION_MS: u64 = 150;
+/// How long a `Success` result stays on screen before auto-advancing to
+/// `Closing`, mirroring `OPEN_DURATION_MS`'s role for the open transition.
+const SUCCESS_DWELL_MS: u64 = 700;
+/// Full fade-in/fade-out period of the search-box caret blink.
+const CARET_BLINK_PERIOD_S: f32 = 1.0;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum MenuState {
     Closed,
     Opening,
     Open,
     Confirming { item_idx: usize },
+    /// A `shell`/`retroarch` action is running on a worker thread; `tick()`
+    /// polls `action_rx` for the result.
+    Executing { item_idx: usize },
+    /// The action completed successfully; dwells for `SUCCESS_DWELL_MS`
+    /// before `tick()` advances to `Closing`.
+    Success { item_idx: usize },
+    /// The action failed; stays open (showing `msg`) until `back()` or
+    /// `select()` dismisses it back to `Open`.
+    Error { item_idx: usize, msg: String },
     Closing,
 }
 
@@ -23,34 +40,305 @@ pub enum MenuAction {
     Shell(String),
 }
 
+/// Result of a worker thread spawned for a `shell`/`retroarch` action.
+enum ActionOutcome {
+    Success,
+    Error(String),
+}
+
+/// Text-input state for the in-menu search/filter box: the query string,
+/// a char-index caret, and the instant the caret last blinked (reset on
+/// every edit/move so typing always shows a solid caret).
+struct SearchInput {
+    query: String,
+    caret: usize,
+    blink_start: Instant,
+}
+
+impl SearchInput {
+    fn new() -> Self {
+        SearchInput { query: String::new(), caret: 0, blink_start: Instant::now() }
+    }
+
+    fn reset_blink(&mut self) {
+        self.blink_start = Instant::now();
+    }
+}
+
 pub struct Menu {
     state: MenuState,
     cursor: usize,
-    items: Vec<MenuItem>,
+    items: Vec<MenuEntry>,
+    /// Ancestor levels for nested submenus: each entry is the parent item
+    /// list and the cursor position within it, pushed on submenu entry and
+    /// popped on `back()`. Empty at the root level.
+    stack: Vec<(Vec<MenuEntry>, usize)>,
     transition_start: Instant,
     dirty: bool,
     /// Tracks when each button was first pressed (for hold detection).
     hold_starts: HashMap<String, Instant>,
+    search: SearchInput,
+    /// Receiving end for the worker thread spawned by `execute_item` for a
+    /// `shell`/`retroarch` action; polled non-blockingly from `tick()`.
+    action_rx: Option<Receiver<ActionOutcome>>,
+    /// RetroArch UDP endpoint used to dispatch `action = "retroarch"` items.
+    /// Set via `set_retroarch_endpoint`; actions fail with `Error` if unset.
+    retroarch_addr: Option<(String, u16)>,
+    /// Cached `visible_if` results, keyed by item id. Absent means "not yet
+    /// evaluated" — treated as visible until the worker thread reports back.
+    visibility: HashMap<String, bool>,
+    /// Item ids with a `visible_if` check currently running on a worker
+    /// thread, so `refresh_visibility` doesn't spawn a duplicate.
+    visibility_pending: HashSet<String>,
+    visibility_tx: Sender<(String, bool)>,
+    visibility_rx: Receiver<(String, bool)>,
+    /// Set once at construction and never reset — the free-running clock
+    /// driving the marquee scroll for menu item labels too long to fit.
+    marquee_start: Instant,
 }
 
 impl Menu {
-    pub fn new(items: Vec<MenuItem>) -> Self {
-        Menu {
+    pub fn new(items: Vec<MenuEntry>) -> Self {
+        let (visibility_tx, visibility_rx) = mpsc::channel();
+        let mut menu = Menu {
             state: MenuState::Closed,
             cursor: 0,
             items,
+            stack: Vec::new(),
             transition_start: Instant::now(),
             dirty: false,
             hold_starts: HashMap::new(),
+            search: SearchInput::new(),
+            action_rx: None,
+            retroarch_addr: None,
+            visibility: HashMap::new(),
+            visibility_pending: HashSet::new(),
+            visibility_tx,
+            visibility_rx,
+            marquee_start: Instant::now(),
+        };
+        menu.cursor = menu.first_selectable();
+        menu
+    }
+
+    /// Milliseconds since this menu was constructed — the marquee clock for
+    /// item labels too long to fit their column.
+    pub fn marquee_elapsed_ms(&self) -> u64 {
+        self.marquee_start.elapsed().as_millis() as u64
+    }
+
+    /// Configure the RetroArch UDP endpoint used to dispatch `retroarch`
+    /// actions. Call once after construction; without it, `retroarch`
+    /// actions resolve immediately to `Error`.
+    pub fn set_retroarch_endpoint(&mut self, host: String, port: u16) {
+        self.retroarch_addr = Some((host, port));
+    }
+
+    /// Push the current item list onto the stack and enter `children` as
+    /// the new level, resetting cursor and search for the child list.
+    fn enter_submenu(&mut self, children: Vec<MenuEntry>) {
+        let parent = std::mem::replace(&mut self.items, children);
+        self.stack.push((parent, self.cursor));
+        self.search = SearchInput::new();
+        self.refresh_visibility();
+        self.cursor = self.first_selectable();
+        self.dirty = true;
+    }
+
+    /// Pop the most recent ancestor level, if any, restoring its item list
+    /// and cursor. Returns whether a level was popped.
+    fn leave_submenu(&mut self) -> bool {
+        let Some((parent, cursor)) = self.stack.pop() else { return false };
+        self.items = parent;
+        self.cursor = cursor;
+        self.search = SearchInput::new();
+        self.dirty = true;
+        true
+    }
+
+    /// Spawn `work` on its own thread and switch to `Executing { item_idx
+    /// }`; `tick()` polls the channel non-blockingly for the result.
+    fn begin_execute(&mut self, item_idx: usize, work: impl FnOnce() -> Result<(), String> + Send + 'static) {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let outcome = match work() {
+                Ok(()) => ActionOutcome::Success,
+                Err(msg) => ActionOutcome::Error(msg),
+            };
+            let _ = tx.send(outcome);
+        });
+        self.action_rx = Some(rx);
+        self.state = MenuState::Executing { item_idx };
+        self.transition_start = Instant::now();
+        self.dirty = true;
+    }
+
+    /// Whether `item` matches the current search query (always true when
+    /// the query is empty). Case-insensitive substring match on label.
+    fn matches_query(&self, item: &MenuEntry) -> bool {
+        self.search.query.is_empty()
+            || item.label().to_lowercase().contains(&self.search.query.to_lowercase())
+    }
+
+    /// Whether `item` has a `visible_if` predicate that has resolved to
+    /// `false`. Pending or absent predicates are not hidden — see
+    /// `refresh_visibility`.
+    fn is_hidden(&self, item: &MenuEntry) -> bool {
+        match item {
+            MenuEntry::Action(it) => {
+                it.visible_if.is_some() && self.visibility.get(&it.id) == Some(&false)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `item` should be drawn given the current filter: everything
+    /// when the query is empty, else only selectable entries that match —
+    /// and never an entry hidden by its `visible_if` predicate.
+    fn item_visible(&self, item: &MenuEntry) -> bool {
+        if self.is_hidden(item) {
+            return false;
+        }
+        self.search.query.is_empty() || (item.is_selectable() && self.matches_query(item))
+    }
+
+    /// Spawn a `visible_if` check for every item at the current level that
+    /// has one and isn't already being checked. Results are cached and
+    /// drained non-blockingly by `tick()`; items stay visible until their
+    /// first result arrives.
+    pub fn refresh_visibility(&mut self) {
+        for item in &self.items {
+            let MenuEntry::Action(it) = item else { continue };
+            let Some(predicate) = it.visible_if.clone() else { continue };
+            if self.visibility_pending.contains(&it.id) {
+                continue;
+            }
+            self.visibility_pending.insert(it.id.clone());
+            let id = it.id.clone();
+            let tx = self.visibility_tx.clone();
+            thread::spawn(move || {
+                let visible = std::process::Command::new("sh")
+                    .args(["-c", &predicate])
+                    .status()
+                    .map(|status| status.success())
+                    .unwrap_or(true);
+                let _ = tx.send((id, visible));
+            });
         }
     }
 
+    /// Whether cursor navigation can land on the entry at `idx`.
+    fn is_navigable(&self, idx: usize) -> bool {
+        self.items[idx].is_selectable() && self.item_visible(&self.items[idx])
+    }
+
+    /// Index of the first navigable entry, or 0 if none are navigable.
+    fn first_selectable(&self) -> usize {
+        (0..self.items.len()).find(|&i| self.is_navigable(i)).unwrap_or(0)
+    }
+
+    /// Walk from `idx` in `dir` (+1/-1), wrapping, to the next navigable
+    /// entry. Returns `idx` unchanged if no entry is navigable.
+    fn next_selectable(&self, idx: usize, dir: isize) -> usize {
+        let len = self.items.len() as isize;
+        if len == 0 {
+            return idx;
+        }
+        let mut i = idx as isize;
+        for _ in 0..len {
+            i = (i + dir).rem_euclid(len);
+            if self.is_navigable(i as usize) {
+                return i as usize;
+            }
+        }
+        idx
+    }
+
+    /// Entries to draw, paired with their index in the full item list (so
+    /// the renderer can still compare against `cursor()`). Unfiltered when
+    /// the search query is empty.
+    pub fn visible_items(&self) -> Vec<(usize, &MenuEntry)> {
+        self.items.iter().enumerate().filter(|(_, it)| self.item_visible(it)).collect()
+    }
+
+    /// The entry the cursor currently points at, if any.
+    pub fn current_item(&self) -> Option<&MenuEntry> {
+        self.items.get(self.cursor)
+    }
+
+    pub fn search_query(&self) -> &str {
+        &self.search.query
+    }
+
+    pub fn search_caret(&self) -> usize {
+        self.search.caret
+    }
+
+    /// Caret alpha (0.0-1.0), pulsing via a time-based cosine so it blinks
+    /// and starts fully opaque right after a reset.
+    pub fn search_caret_alpha(&self) -> f32 {
+        let t = self.search.blink_start.elapsed().as_secs_f32();
+        (t * std::f32::consts::TAU / CARET_BLINK_PERIOD_S).cos() * 0.5 + 0.5
+    }
+
+    /// Insert `ch` at the caret and re-home the cursor onto the filtered set.
+    pub fn push_char(&mut self, ch: char) {
+        if !matches!(self.state, MenuState::Open) || ch.is_control() {
+            return;
+        }
+        let byte_idx = self.search.query.char_indices().nth(self.search.caret)
+            .map(|(i, _)| i).unwrap_or(self.search.query.len());
+        self.search.query.insert(byte_idx, ch);
+        self.search.caret += 1;
+        self.on_query_changed();
+    }
+
+    /// Delete the character before the caret, if any.
+    pub fn backspace(&mut self) {
+        if !matches!(self.state, MenuState::Open) || self.search.caret == 0 {
+            return;
+        }
+        let mut chars: Vec<char> = self.search.query.chars().collect();
+        chars.remove(self.search.caret - 1);
+        self.search.query = chars.into_iter().collect();
+        self.search.caret -= 1;
+        self.on_query_changed();
+    }
+
+    pub fn move_caret_left(&mut self) {
+        if self.search.caret > 0 {
+            self.search.caret -= 1;
+        }
+        self.search.reset_blink();
+        self.dirty = true;
+    }
+
+    pub fn move_caret_right(&mut self) {
+        let len = self.search.query.chars().count();
+        if self.search.caret < len {
+            self.search.caret += 1;
+        }
+        self.search.reset_blink();
+        self.dirty = true;
+    }
+
+    fn on_query_changed(&mut self) {
+        self.search.reset_blink();
+        self.cursor = self.first_selectable();
+        self.dirty = true;
+    }
+
     pub fn toggle(&mut self) {
-        match self.state {
+        match &self.state {
             MenuState::Closed => {
+                while let Some((parent, _)) = self.stack.pop() {
+                    self.items = parent;
+                }
                 self.state = MenuState::Opening;
                 self.transition_start = Instant::now();
-                self.cursor = 0;
+                self.search = SearchInput::new();
+                self.refresh_visibility();
+                self.cursor = self.first_selectable();
                 self.dirty = true;
             }
             MenuState::Open | MenuState::Confirming { .. } => {
@@ -69,11 +357,7 @@ impl Menu {
         if self.items.is_empty() {
             return;
         }
-        self.cursor = if self.cursor == 0 {
-            self.items.len() - 1
-        } else {
-            self.cursor - 1
-        };
+        self.cursor = self.next_selectable(self.cursor, -1);
         self.dirty = true;
     }
 
@@ -84,7 +368,7 @@ impl Menu {
         if self.items.is_empty() {
             return;
         }
-        self.cursor = (self.cursor + 1) % self.items.len();
+        self.cursor = self.next_selectable(self.cursor, 1);
         self.dirty = true;
     }
 
@@ -94,24 +378,33 @@ impl Menu {
             return None;
         }
 
-        match self.state {
+        match &self.state {
             MenuState::Open => {
-                let item = &self.items[self.cursor];
-                if item.confirm {
-                    self.state = MenuState::Confirming { item_idx: self.cursor };
-                    self.dirty = true;
-                    return None;
+                if let MenuEntry::Action(item) = &self.items[self.cursor] {
+                    if item.confirm {
+                        self.state = MenuState::Confirming { item_idx: self.cursor };
+                        self.dirty = true;
+                        return None;
+                    }
                 }
                 self.execute_item(self.cursor)
             }
-            MenuState::Confirming { item_idx } => self.execute_item(item_idx),
+            MenuState::Confirming { item_idx } => self.execute_item(*item_idx),
+            MenuState::Error { .. } => {
+                self.state = MenuState::Open;
+                self.dirty = true;
+                None
+            }
             _ => None,
         }
     }
 
     pub fn back(&mut self) {
-        match self.state {
+        match &self.state {
             MenuState::Open => {
+                if self.leave_submenu() {
+                    return;
+                }
                 self.state = MenuState::Closing;
                 self.transition_start = Instant::now();
                 self.dirty = true;
@@ -120,13 +413,30 @@ impl Menu {
                 self.state = MenuState::Open;
                 self.dirty = true;
             }
+            MenuState::Error { .. } => {
+                self.state = MenuState::Open;
+                self.dirty = true;
+            }
             _ => {}
         }
     }
 
     pub fn tick(&mut self) {
+        let mut visibility_changed = false;
+        while let Ok((id, visible)) = self.visibility_rx.try_recv() {
+            self.visibility_pending.remove(&id);
+            self.visibility.insert(id, visible);
+            visibility_changed = true;
+        }
+        if visibility_changed {
+            if !self.items.is_empty() && !self.is_navigable(self.cursor) {
+                self.cursor = self.first_selectable();
+            }
+            self.dirty = true;
+        }
+
         let elapsed = self.transition_start.elapsed().as_millis() as u64;
-        match self.state {
+        match &self.state {
             MenuState::Opening if elapsed >= OPEN_DURATION_MS => {
                 self.state = MenuState::Open;
                 self.dirty = true;
@@ -138,6 +448,24 @@ impl Menu {
             MenuState::Opening | MenuState::Closing => {
                 self.dirty = true; // Still animating
             }
+            MenuState::Executing { item_idx } => {
+                let item_idx = *item_idx;
+                let outcome = self.action_rx.as_ref().and_then(|rx| rx.try_recv().ok());
+                if let Some(outcome) = outcome {
+                    self.action_rx = None;
+                    self.state = match outcome {
+                        ActionOutcome::Success => MenuState::Success { item_idx },
+                        ActionOutcome::Error(msg) => MenuState::Error { item_idx, msg },
+                    };
+                    self.transition_start = Instant::now();
+                }
+                self.dirty = true; // Spinner animates regardless
+            }
+            MenuState::Success { .. } if elapsed >= SUCCESS_DWELL_MS => {
+                self.state = MenuState::Closing;
+                self.transition_start = Instant::now();
+                self.dirty = true;
+            }
             _ => {}
         }
     }
@@ -151,16 +479,20 @@ impl Menu {
     }
 
     pub fn state(&self) -> MenuState {
-        self.state
+        self.state.clone()
     }
 
     /// Opacity for fade transitions (0.0 to 1.0).
     pub fn opacity(&self) -> f32 {
         let elapsed = self.transition_start.elapsed().as_millis() as f32;
-        match self.state {
+        match &self.state {
             MenuState::Closed => 0.0,
             MenuState::Opening => (elapsed / OPEN_DURATION_MS as f32).min(1.0),
-            MenuState::Open | MenuState::Confirming { .. } => 1.0,
+            MenuState::Open
+            | MenuState::Confirming { .. }
+            | MenuState::Executing { .. }
+            | MenuState::Success { .. }
+            | MenuState::Error { .. } => 1.0,
             MenuState::Closing => 1.0 - (elapsed / CLOSE_DURATION_MS as f32).min(1.0),
         }
     }
@@ -170,10 +502,22 @@ impl Menu {
         0.95 + 0.05 * self.opacity()
     }
 
-    pub fn items(&self) -> &[MenuItem] {
+    pub fn items(&self) -> &[MenuEntry] {
         &self.items
     }
 
+    /// Replace the item list wholesale (e.g. when pushed dynamically over
+    /// the socket). Resets the cursor and search filter to avoid pointing
+    /// past the new list.
+    pub fn set_items(&mut self, items: Vec<MenuEntry>) {
+        self.items = items;
+        self.stack.clear();
+        self.search = SearchInput::new();
+        self.refresh_visibility();
+        self.cursor = self.first_selectable();
+        self.dirty = true;
+    }
+
     pub fn is_dirty(&self) -> bool {
         self.dirty
     }
@@ -182,16 +526,23 @@ impl Menu {
         self.dirty = false;
     }
 
-    /// Activate a quick-press binding. Returns the action if a menu item has
-    /// `bind` matching the given button name.
-    pub fn activate_bind(&mut self, button: &str) -> Option<MenuAction> {
+    /// Activate a quick-press binding. `button` is the button that was just
+    /// released (the trigger); `held` is every other button still held at
+    /// that moment, used to satisfy a chord's modifiers. Returns the action
+    /// if a menu item's `bind` matches `button` and, for a chord binding,
+    /// every modifier in its `chord` is present in `held`.
+    pub fn activate_bind(&mut self, button: &str, held: &[&str]) -> Option<MenuAction> {
         if !matches!(self.state, MenuState::Open | MenuState::Confirming { .. }) {
             return None;
         }
         let idx = self.items.iter().position(|it| {
-            it.bind.as_deref() == Some(button)
+            !self.is_hidden(it)
+                && match it {
+                    MenuEntry::Action(item) => item.bind.as_deref() == Some(button) && chord_satisfied(item, held),
+                    _ => false,
+                }
         })?;
-        let item = &self.items[idx];
+        let MenuEntry::Action(item) = &self.items[idx] else { return None };
         if item.confirm {
             self.cursor = idx;
             self.state = MenuState::Confirming { item_idx: idx };
@@ -217,20 +568,28 @@ impl Menu {
         self.dirty = true;
     }
 
-    /// Check if any hold binding has reached its threshold.
-    /// Call from tick(). Returns the action if a hold completed.
-    pub fn check_holds(&mut self) -> Option<MenuAction> {
+    /// Check if any hold binding has reached its threshold. `held` is every
+    /// button currently down, used to satisfy a chord's modifiers. Call
+    /// from tick(). Returns the action if a hold completed.
+    pub fn check_holds(&mut self, held: &[&str]) -> Option<MenuAction> {
         if !matches!(self.state, MenuState::Open | MenuState::Confirming { .. }) {
             self.hold_starts.clear();
             return None;
         }
 
         for idx in 0..self.items.len() {
-            let btn = match self.items[idx].hold_bind.as_deref() {
+            let MenuEntry::Action(item) = &self.items[idx] else { continue };
+            if self.is_hidden(&self.items[idx]) {
+                continue;
+            }
+            let btn = match item.hold_bind.as_deref() {
                 Some(b) => b.to_string(),
                 None => continue,
             };
-            let threshold = self.items[idx].hold_ms;
+            if !chord_satisfied(item, held) {
+                continue;
+            }
+            let threshold = item.hold_ms;
             if let Some(start) = self.hold_starts.get(&btn) {
                 if start.elapsed().as_millis() as u64 >= threshold {
                     self.hold_starts.remove(&btn);
@@ -244,8 +603,10 @@ impl Menu {
     /// Get hold progress (0.0..1.0) for a given button name, for rendering.
     pub fn hold_progress(&self, button: &str) -> f32 {
         let threshold = self.items.iter()
-            .find(|it| it.hold_bind.as_deref() == Some(button))
-            .map(|it| it.hold_ms)
+            .find_map(|it| match it {
+                MenuEntry::Action(item) if item.hold_bind.as_deref() == Some(button) => Some(item.hold_ms),
+                _ => None,
+            })
             .unwrap_or(1500);
         self.hold_starts.get(button)
             .map(|start| (start.elapsed().as_millis() as f32 / threshold as f32).min(1.0))
@@ -256,10 +617,14 @@ impl Menu {
     pub fn bound_items(&self) -> Vec<(&MenuItem, bool)> {
         self.items.iter()
             .filter_map(|it| {
-                if it.bind.is_some() {
-                    Some((it, false))
-                } else if it.hold_bind.is_some() {
-                    Some((it, true))
+                let MenuEntry::Action(item) = it else { return None };
+                if self.is_hidden(it) {
+                    return None;
+                }
+                if item.bind.is_some() {
+                    Some((item, false))
+                } else if item.hold_bind.is_some() {
+                    Some((item, true))
                 } else {
                     None
                 }
@@ -267,34 +632,134 @@ impl Menu {
             .collect()
     }
 
+    /// Run the action for the entry at `idx`. Quick-settings controls
+    /// (toggle/cycle/slider) adjust in place and leave the menu open; a
+    /// `submenu` entry pushes its children as the new level; `dismiss`
+    /// closes the menu immediately; `shell`/`retroarch` run on a worker
+    /// thread and transition to `Executing` while they're in flight.
     fn execute_item(&mut self, idx: usize) -> Option<MenuAction> {
-        let item = &self.items[idx];
-        let action = match item.action.as_str() {
-            "dismiss" => Some(MenuAction::Dismiss),
-            "retroarch" => item.command.as_ref().map(|c| MenuAction::RetroArch(c.clone())),
-            "shell" => item.command.as_ref().map(|c| MenuAction::Shell(c.clone())),
-            _ => None,
+        match &mut self.items[idx] {
+            MenuEntry::Toggle(t) => {
+                t.value = !t.value;
+                self.dirty = true;
+                return None;
+            }
+            MenuEntry::OptionCycle(c) => {
+                if !c.options.is_empty() {
+                    c.selected = (c.selected + 1) % c.options.len();
+                }
+                self.dirty = true;
+                return None;
+            }
+            MenuEntry::Slider(s) => {
+                s.value = if s.value >= 0.95 { 0.0 } else { (s.value + 0.1).min(1.0) };
+                self.dirty = true;
+                return None;
+            }
+            _ => {}
+        }
+
+        let (kind, command) = match &self.items[idx] {
+            MenuEntry::Action(item) => (item.action, item.command.clone()),
+            _ => return None,
         };
 
-        // Close menu after action
-        self.state = MenuState::Closing;
-        self.transition_start = Instant::now();
-        self.dirty = true;
+        handler_for(kind)(self, idx, command)
+    }
+}
 
-        action
+/// Executes one `ActionKind`, given the item's index (for `begin_execute`'s
+/// `Executing { item_idx }` state) and its parsed `command` string.
+type ActionHandler = fn(&mut Menu, usize, Option<String>) -> Option<MenuAction>;
+
+/// Maps a parsed `ActionKind` to the handler that executes it. New kinds
+/// slot in by adding a variant, a handler function, and an arm here —
+/// `execute_item` itself never needs to change.
+fn handler_for(kind: ActionKind) -> ActionHandler {
+    match kind {
+        ActionKind::Dismiss => handle_dismiss,
+        ActionKind::RetroArch => handle_retroarch,
+        ActionKind::Shell => handle_shell,
+        ActionKind::Submenu => handle_submenu,
     }
 }
 
+fn handle_dismiss(menu: &mut Menu, _idx: usize, _command: Option<String>) -> Option<MenuAction> {
+    menu.state = MenuState::Closing;
+    menu.transition_start = Instant::now();
+    menu.dirty = true;
+    Some(MenuAction::Dismiss)
+}
+
+fn handle_retroarch(menu: &mut Menu, idx: usize, command: Option<String>) -> Option<MenuAction> {
+    let cmd = command?;
+    let addr = menu.retroarch_addr.clone();
+    let cmd_for_thread = cmd.clone();
+    menu.begin_execute(idx, move || {
+        let (host, port) = addr.ok_or_else(|| "RetroArch endpoint not configured".to_string())?;
+        let client = RetroArchClient::new(&host, port)?;
+        if client.send_command(&cmd_for_thread) {
+            Ok(())
+        } else {
+            Err(format!("failed to send '{cmd_for_thread}' to RetroArch"))
+        }
+    });
+    Some(MenuAction::RetroArch(cmd))
+}
+
+fn handle_shell(menu: &mut Menu, idx: usize, command: Option<String>) -> Option<MenuAction> {
+    let cmd = command?;
+    let cmd_for_thread = cmd.clone();
+    menu.begin_execute(idx, move || run_shell_command(&cmd_for_thread));
+    Some(MenuAction::Shell(cmd))
+}
+
+fn handle_submenu(menu: &mut Menu, idx: usize, _command: Option<String>) -> Option<MenuAction> {
+    let MenuEntry::Action(item) = &menu.items[idx] else { return None };
+    let children = item.items.clone();
+    menu.enter_submenu(children);
+    None
+}
+
+/// True if `item`'s `chord` (if any) has every modifier present in `held`.
+/// A plain (non-chord) binding always passes — `chord` only adds an extra
+/// condition on top of the `bind`/`hold_bind` trigger match.
+fn chord_satisfied(item: &MenuItem, held: &[&str]) -> bool {
+    let Some(chord) = &item.chord else { return true };
+    let mut parts = chord.split('+');
+    parts.next_back(); // trigger, already matched via bind/hold_bind
+    parts.all(|modifier| held.contains(&modifier))
+}
+
+/// Run `cmd` via `sh -c`, reporting a non-zero exit (or spawn failure) as
+/// an error with captured stderr — the outcome sent back over
+/// `Menu::begin_execute`'s channel for an `action = "shell"` item.
+fn run_shell_command(cmd: &str) -> Result<(), String> {
+    let output = std::process::Command::new("sh")
+        .args(["-c", cmd])
+        .output()
+        .map_err(|e| format!("failed to run '{cmd}': {e}"))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Err(if stderr.is_empty() {
+        format!("'{cmd}' exited with {}", output.status)
+    } else {
+        stderr
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::MenuItem;
+    use crate::config::{MenuItem, OptionCycleEntry, ToggleEntry};
 
-    fn test_items() -> Vec<MenuItem> {
+    fn test_items() -> Vec<MenuEntry> {
         vec![
-            MenuItem { id: "resume".into(), label: "Resume".into(), icon: None, action: "dismiss".into(), command: None, confirm: false, bind: Some("b".into()), hold_bind: None, hold_ms: 1500, hint_label: None },
-            MenuItem { id: "save".into(), label: "Save State".into(), icon: None, action: "retroarch".into(), command: Some("SAVE_STATE".into()), confirm: false, bind: None, hold_bind: Some("y".into()), hold_ms: 1500, hint_label: Some("Save".into()) },
-            MenuItem { id: "quit".into(), label: "Quit".into(), icon: None, action: "retroarch".into(), command: Some("QUIT".into()), confirm: true, bind: None, hold_bind: Some("start".into()), hold_ms: 2000, hint_label: Some("Quit".into()) },
+            MenuEntry::Action(MenuItem { id: "resume".into(), label: "Resume".into(), icon: None, action: ActionKind::Dismiss, command: None, confirm: false, bind: Some("b".into()), hold_bind: None, chord: None, hold_ms: 1500, hint_label: None, visible_if: None, items: Vec::new() }),
+            MenuEntry::Action(MenuItem { id: "save".into(), label: "Save State".into(), icon: None, action: ActionKind::RetroArch, command: Some("SAVE_STATE".into()), confirm: false, bind: None, hold_bind: Some("y".into()), chord: None, hold_ms: 1500, hint_label: Some("Save".into()), visible_if: None, items: Vec::new() }),
+            MenuEntry::Action(MenuItem { id: "quit".into(), label: "Quit".into(), icon: None, action: ActionKind::RetroArch, command: Some("QUIT".into()), confirm: true, bind: None, hold_bind: Some("start".into()), chord: None, hold_ms: 2000, hint_label: Some("Quit".into()), visible_if: None, items: Vec::new() }),
         ]
     }
 
@@ -376,4 +841,231 @@ mod tests {
         menu.back(); // Cancel
         assert!(matches!(menu.state(), MenuState::Open));
     }
+
+    #[test]
+    fn navigation_skips_non_selectable_entries() {
+        let mut items = test_items();
+        items.insert(1, MenuEntry::Header(crate::config::HeaderEntry { header: "Display".into() }));
+        items.insert(0, MenuEntry::Spacer(crate::config::SpacerEntry { spacer: 8.0 }));
+        let mut menu = Menu::new(items);
+        menu.state = MenuState::Open;
+
+        // new() should have skipped past the leading spacer
+        assert_eq!(menu.cursor(), 1);
+
+        menu.move_down(); // skip the header entry
+        assert_eq!(menu.cursor(), 3); // "save" (index shifted by the two inserts)
+
+        menu.move_up();
+        assert_eq!(menu.cursor(), 1); // back to "resume", skipping header
+    }
+
+    #[test]
+    fn select_toggle_flips_value_and_keeps_menu_open() {
+        let mut items = test_items();
+        items.push(MenuEntry::Toggle(ToggleEntry { toggle: "Fullscreen".into(), value: false }));
+        let mut menu = Menu::new(items);
+        menu.state = MenuState::Open;
+        menu.cursor = 3;
+
+        let action = menu.select();
+        assert!(action.is_none());
+        assert!(matches!(menu.state(), MenuState::Open));
+        match &menu.items()[3] {
+            MenuEntry::Toggle(t) => assert!(t.value),
+            _ => panic!("expected Toggle"),
+        }
+    }
+
+    #[test]
+    fn select_option_cycle_advances_and_wraps() {
+        let mut items = test_items();
+        items.push(MenuEntry::OptionCycle(OptionCycleEntry {
+            option_cycle: "Aspect Ratio".into(),
+            selected: 1,
+            options: vec!["4:3".into(), "16:9".into()],
+        }));
+        let mut menu = Menu::new(items);
+        menu.state = MenuState::Open;
+        menu.cursor = 3;
+
+        menu.select();
+        match &menu.items()[3] {
+            MenuEntry::OptionCycle(c) => assert_eq!(c.selected, 0), // wrapped
+            _ => panic!("expected OptionCycle"),
+        }
+    }
+
+    #[test]
+    fn typing_filters_visible_items_and_remaps_cursor() {
+        let mut menu = Menu::new(test_items());
+        menu.state = MenuState::Open;
+
+        for ch in "save".chars() {
+            menu.push_char(ch);
+        }
+
+        assert_eq!(menu.search_query(), "save");
+        let visible: Vec<&str> = menu.visible_items().iter().map(|(_, it)| it.label()).collect();
+        assert_eq!(visible, vec!["Save State"]);
+        assert_eq!(menu.current_item().unwrap().label(), "Save State");
+    }
+
+    #[test]
+    fn backspace_undoes_filter() {
+        let mut menu = Menu::new(test_items());
+        menu.state = MenuState::Open;
+
+        menu.push_char('x'); // matches nothing
+        assert!(menu.visible_items().is_empty());
+
+        menu.backspace();
+        assert_eq!(menu.search_query(), "");
+        assert_eq!(menu.visible_items().len(), 3);
+    }
+
+    #[test]
+    fn closing_and_reopening_resets_search() {
+        let mut menu = Menu::new(test_items());
+        menu.state = MenuState::Open;
+        menu.push_char('q');
+        assert_eq!(menu.search_query(), "q");
+
+        menu.state = MenuState::Closed;
+        menu.toggle();
+        assert_eq!(menu.search_query(), "");
+    }
+
+    #[test]
+    fn selecting_submenu_item_pushes_children_and_stays_open() {
+        let mut items = test_items();
+        items.push(MenuEntry::Action(MenuItem {
+            id: "display".into(), label: "Display".into(), icon: None, action: ActionKind::Submenu,
+            command: None, confirm: false, bind: None, hold_bind: None, chord: None, hold_ms: 1500,
+            hint_label: None, visible_if: None, items: test_items(),
+        }));
+        let mut menu = Menu::new(items);
+        menu.state = MenuState::Open;
+        menu.cursor = 3;
+
+        let action = menu.select();
+        assert!(action.is_none());
+        assert!(matches!(menu.state(), MenuState::Open));
+        assert_eq!(menu.items().len(), 3);
+        assert_eq!(menu.current_item().unwrap().label(), "Resume");
+    }
+
+    #[test]
+    fn back_pops_submenu_before_closing() {
+        let mut items = test_items();
+        items.push(MenuEntry::Action(MenuItem {
+            id: "display".into(), label: "Display".into(), icon: None, action: ActionKind::Submenu,
+            command: None, confirm: false, bind: None, hold_bind: None, chord: None, hold_ms: 1500,
+            hint_label: None, visible_if: None, items: vec![MenuEntry::Action(MenuItem {
+                id: "brightness".into(), label: "Brightness".into(), icon: None, action: ActionKind::Dismiss,
+                command: None, confirm: false, bind: None, hold_bind: None, chord: None, hold_ms: 1500,
+                hint_label: None, visible_if: None, items: Vec::new(),
+            })],
+        }));
+        let mut menu = Menu::new(items);
+        menu.state = MenuState::Open;
+        menu.cursor = 3;
+        menu.select();
+        assert_eq!(menu.current_item().unwrap().label(), "Brightness");
+
+        // First back() pops the submenu, restoring the root level and cursor.
+        menu.back();
+        assert!(matches!(menu.state(), MenuState::Open));
+        assert_eq!(menu.cursor(), 3);
+        assert_eq!(menu.current_item().unwrap().label(), "Display");
+
+        // Second back() at the root closes the menu as before.
+        menu.back();
+        assert!(matches!(menu.state(), MenuState::Closing));
+    }
+
+    #[test]
+    fn shell_action_succeeds_then_dwells_before_closing() {
+        let mut items = test_items();
+        items.push(MenuEntry::Action(MenuItem {
+            id: "noop".into(), label: "No-op".into(), icon: None, action: ActionKind::Shell,
+            command: Some("true".into()), confirm: false, bind: None, hold_bind: None, chord: None,
+            hold_ms: 1500, hint_label: None, visible_if: None, items: Vec::new(),
+        }));
+        let mut menu = Menu::new(items);
+        menu.state = MenuState::Open;
+        menu.cursor = 3;
+
+        let action = menu.select();
+        assert!(matches!(action, Some(MenuAction::Shell(ref c)) if c == "true"));
+        assert!(matches!(menu.state(), MenuState::Executing { item_idx: 3 }));
+
+        // Give the worker thread time to run `true` and report success.
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        menu.tick();
+        assert!(matches!(menu.state(), MenuState::Success { item_idx: 3 }));
+
+        // After the dwell period, tick() advances to Closing on its own.
+        std::thread::sleep(std::time::Duration::from_millis(750));
+        menu.tick();
+        assert!(matches!(menu.state(), MenuState::Closing));
+    }
+
+    #[test]
+    fn shell_action_reports_error_and_stays_open_until_dismissed() {
+        let mut items = test_items();
+        items.push(MenuEntry::Action(MenuItem {
+            id: "boom".into(), label: "Boom".into(), icon: None, action: ActionKind::Shell,
+            command: Some("exit 1".into()), confirm: false, bind: None, hold_bind: None, chord: None,
+            hold_ms: 1500, hint_label: None, visible_if: None, items: Vec::new(),
+        }));
+        let mut menu = Menu::new(items);
+        menu.state = MenuState::Open;
+        menu.cursor = 3;
+
+        menu.select();
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        menu.tick();
+        assert!(matches!(menu.state(), MenuState::Error { item_idx: 3, .. }));
+
+        menu.back();
+        assert!(matches!(menu.state(), MenuState::Open));
+    }
+
+    #[test]
+    fn hidden_item_starts_visible_then_disappears_once_predicate_resolves() {
+        let mut items = test_items();
+        items.push(MenuEntry::Action(MenuItem {
+            id: "conditional".into(), label: "Conditional".into(), icon: None, action: ActionKind::Dismiss,
+            command: None, confirm: false, bind: None, hold_bind: None, chord: None, hold_ms: 1500,
+            hint_label: None, visible_if: Some("exit 1".into()), items: Vec::new(),
+        }));
+        let mut menu = Menu::new(items);
+        menu.toggle(); // Opening spawns the visible_if check
+
+        // Before the worker thread reports back, the item is still shown.
+        assert_eq!(menu.visible_items().len(), 4);
+
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        menu.tick();
+        let visible: Vec<&str> = menu.visible_items().iter().map(|(_, it)| it.label()).collect();
+        assert_eq!(visible, vec!["Resume", "Save State", "Quit"]);
+    }
+
+    #[test]
+    fn visible_item_stays_shown_once_predicate_resolves_true() {
+        let mut items = test_items();
+        items.push(MenuEntry::Action(MenuItem {
+            id: "conditional".into(), label: "Conditional".into(), icon: None, action: ActionKind::Dismiss,
+            command: None, confirm: false, bind: None, hold_bind: None, chord: None, hold_ms: 1500,
+            hint_label: None, visible_if: Some("true".into()), items: Vec::new(),
+        }));
+        let mut menu = Menu::new(items);
+        menu.toggle();
+
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        menu.tick();
+        let visible: Vec<&str> = menu.visible_items().iter().map(|(_, it)| it.label()).collect();
+        assert_eq!(visible, vec!["Resume", "Save State", "Quit", "Conditional"]);
+    }
 }
@@ -1,10 +1,16 @@
 //! Popup queue and animation timing.
-//! Manages a queue of achievement popups with slide-in, hold, and fade-out phases.
+//!
+//! Holds a priority ring buffer of achievement toasts: up to `max_visible`
+//! are shown at once (stacked with a vertical offset by the renderer) while
+//! the rest wait their turn. A higher-priority toast arriving while all
+//! slots are full preempts the lowest-priority visible one, pushing it back
+//! into the pending queue rather than dropping it.
 
-use std::time::{Duration, Instant};
+use std::collections::VecDeque;
+use std::time::Duration;
 
 const SLIDE_IN_MS: u64 = 300;
-const HOLD_MS: u64 = 4000;
+const DEFAULT_HOLD_MS: u64 = 4000;
 const FADE_OUT_MS: u64 = 500;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -19,7 +25,13 @@ enum Phase {
 pub struct Popup {
     pub title: String,
     pub description: String,
-    started: Instant,
+    pub badge_png: Option<Vec<u8>>,
+    pub priority: i32,
+    /// RetroAchievements achievement id, when known — lets `ra_api`'s async
+    /// badge fetch find this already-queued popup again once it completes.
+    pub id: Option<String>,
+    hold_ms: u64,
+    elapsed: Duration,
     phase: Phase,
 }
 
@@ -28,21 +40,60 @@ impl Popup {
         Popup {
             title,
             description,
-            started: Instant::now(),
+            badge_png: None,
+            priority: 0,
+            id: None,
+            hold_ms: DEFAULT_HOLD_MS,
+            elapsed: Duration::ZERO,
             phase: Phase::SlideIn,
         }
     }
 
+    /// Milliseconds since this popup entered its current slide/hold/fade
+    /// cycle — used as the marquee clock for titles too long to fit.
+    pub fn elapsed_ms(&self) -> u64 {
+        self.elapsed.as_millis() as u64
+    }
+
+    /// Attach a badge image (raw PNG bytes) to display next to the title.
+    pub fn with_badge(mut self, png_bytes: Vec<u8>) -> Self {
+        self.badge_png = Some(png_bytes);
+        self
+    }
+
+    /// Tag this popup with its RetroAchievements achievement id.
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Set the preemption priority (higher wins a visible slot first).
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Override how long the toast holds fully visible before fading out.
+    pub fn with_hold_ms(mut self, hold_ms: u64) -> Self {
+        self.hold_ms = hold_ms;
+        self
+    }
+
+    /// Skip straight to the held (fully visible, pre-fade) phase — used by
+    /// the preview tool to render a static frame without racing real time.
+    pub fn force_hold(&mut self) {
+        self.phase = Phase::Hold;
+        self.elapsed = Duration::from_millis(SLIDE_IN_MS);
+    }
+
     /// Current opacity (0.0 to 1.0) based on animation phase.
     pub fn opacity(&self) -> f32 {
-        let elapsed = self.started.elapsed().as_millis() as u64;
+        let elapsed = self.elapsed.as_millis() as u64;
         match self.phase {
-            Phase::SlideIn => {
-                (elapsed as f32 / SLIDE_IN_MS as f32).min(1.0)
-            }
+            Phase::SlideIn => (elapsed as f32 / SLIDE_IN_MS as f32).min(1.0),
             Phase::Hold => 1.0,
             Phase::FadeOut => {
-                let fade_elapsed = elapsed.saturating_sub(SLIDE_IN_MS + HOLD_MS);
+                let fade_elapsed = elapsed.saturating_sub(SLIDE_IN_MS + self.hold_ms);
                 1.0 - (fade_elapsed as f32 / FADE_OUT_MS as f32).min(1.0)
             }
             Phase::Done => 0.0,
@@ -51,7 +102,7 @@ impl Popup {
 
     /// Horizontal slide offset (0.0 = fully visible, 1.0 = off-screen right).
     pub fn slide_offset(&self) -> f32 {
-        let elapsed = self.started.elapsed().as_millis() as u64;
+        let elapsed = self.elapsed.as_millis() as u64;
         match self.phase {
             Phase::SlideIn => {
                 let t = (elapsed as f32 / SLIDE_IN_MS as f32).min(1.0);
@@ -62,13 +113,14 @@ impl Popup {
         }
     }
 
-    fn tick(&mut self) {
-        let elapsed = self.started.elapsed().as_millis() as u64;
+    fn tick(&mut self, dt: Duration) {
+        self.elapsed += dt;
+        let elapsed = self.elapsed.as_millis() as u64;
         self.phase = if elapsed < SLIDE_IN_MS {
             Phase::SlideIn
-        } else if elapsed < SLIDE_IN_MS + HOLD_MS {
+        } else if elapsed < SLIDE_IN_MS + self.hold_ms {
             Phase::Hold
-        } else if elapsed < SLIDE_IN_MS + HOLD_MS + FADE_OUT_MS {
+        } else if elapsed < SLIDE_IN_MS + self.hold_ms + FADE_OUT_MS {
             Phase::FadeOut
         } else {
             Phase::Done
@@ -78,38 +130,125 @@ impl Popup {
     fn is_done(&self) -> bool {
         self.phase == Phase::Done
     }
+
+    /// Restart the slide-in/hold/fade-out clock — called whenever a popup
+    /// (re)enters a visible slot.
+    fn reset_timing(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.phase = Phase::SlideIn;
+    }
 }
 
+/// Priority ring buffer of pending/visible toasts.
 #[derive(Debug)]
 pub struct PopupQueue {
-    queue: Vec<Popup>,
+    visible: VecDeque<Popup>,
+    pending: VecDeque<Popup>,
+    max_visible: usize,
 }
 
 impl PopupQueue {
     pub fn new() -> Self {
-        PopupQueue { queue: Vec::new() }
+        PopupQueue::with_max_visible(1)
     }
 
-    pub fn push(&mut self, popup: Popup) {
-        self.queue.push(popup);
+    pub fn with_max_visible(max_visible: usize) -> Self {
+        PopupQueue {
+            visible: VecDeque::new(),
+            pending: VecDeque::new(),
+            max_visible: max_visible.max(1),
+        }
+    }
+
+    /// Build a queue with the given popups already in visible slots,
+    /// bypassing push/preemption and timing resets — used by the preview
+    /// tool and tests to pin an exact animation phase for a snapshot.
+    pub fn with_visible(popups: Vec<Popup>) -> Self {
+        let max_visible = popups.len().max(1);
+        PopupQueue {
+            visible: popups.into(),
+            pending: VecDeque::new(),
+            max_visible,
+        }
     }
 
-    /// Advance animation state, remove finished popups.
-    pub fn tick(&mut self) {
-        if let Some(popup) = self.queue.first_mut() {
-            popup.tick();
-            if popup.is_done() {
-                self.queue.remove(0);
-                // Start the next popup immediately
-                if let Some(next) = self.queue.first_mut() {
-                    next.started = std::time::Instant::now();
-                }
+    /// Queue a new toast. If there's a free visible slot it starts sliding in
+    /// immediately; otherwise, if it outranks the lowest-priority visible
+    /// toast, it preempts that slot and bumps the displaced toast back into
+    /// the pending queue. Otherwise it waits in priority order.
+    pub fn push(&mut self, mut popup: Popup) {
+        if self.visible.len() < self.max_visible {
+            popup.reset_timing();
+            self.visible.push_back(popup);
+            return;
+        }
+
+        let lowest_idx = self.visible.iter().enumerate()
+            .min_by_key(|(_, p)| p.priority)
+            .map(|(idx, _)| idx);
+
+        if let Some(lowest_idx) = lowest_idx {
+            if popup.priority > self.visible[lowest_idx].priority {
+                let mut displaced = self.visible.remove(lowest_idx).expect("index in bounds");
+                displaced.reset_timing();
+                self.insert_pending(displaced);
+                popup.reset_timing();
+                self.visible.insert(lowest_idx, popup);
+                return;
             }
         }
+
+        self.insert_pending(popup);
+    }
+
+    /// Insert into the pending queue, highest priority first.
+    fn insert_pending(&mut self, popup: Popup) {
+        let idx = self.pending.iter()
+            .position(|p| p.priority < popup.priority)
+            .unwrap_or(self.pending.len());
+        self.pending.insert(idx, popup);
     }
 
-    /// Get the currently displaying popup, if any.
+    /// Advance all visible toasts, pop finished ones, and promote the
+    /// next-highest-priority pending toast into any freed slot.
+    pub fn tick(&mut self, dt: Duration) {
+        for popup in self.visible.iter_mut() {
+            popup.tick(dt);
+        }
+        self.visible.retain(|p| !p.is_done());
+
+        while self.visible.len() < self.max_visible {
+            let Some(mut next) = self.pending.pop_front() else { break };
+            next.reset_timing();
+            self.visible.push_back(next);
+        }
+    }
+
+    /// Currently visible toasts, in stacking order (oldest/topmost first).
+    pub fn visible(&self) -> impl Iterator<Item = &Popup> {
+        self.visible.iter()
+    }
+
+    /// The topmost visible toast, for callers that only render one at a time.
     pub fn current(&self) -> Option<&Popup> {
-        self.queue.first()
+        self.visible.front()
+    }
+
+    /// Attach badge art to the already-queued popup tagged with `id`,
+    /// whether it's currently visible or still pending — used by `ra_api`'s
+    /// async fetch, which completes after the scraped `Unlock` event has
+    /// already pushed a text-only popup.
+    pub fn set_badge(&mut self, id: &str, badge_png: Vec<u8>) {
+        let popup = self.visible.iter_mut().chain(self.pending.iter_mut())
+            .find(|p| p.id.as_deref() == Some(id));
+        if let Some(popup) = popup {
+            popup.badge_png = Some(badge_png);
+        }
+    }
+}
+
+impl Default for PopupQueue {
+    fn default() -> Self {
+        PopupQueue::new()
     }
 }
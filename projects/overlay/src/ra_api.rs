@@ -0,0 +1,86 @@
+//! Optional RetroAchievements Web API data source.
+//!
+//! Log scraping (`watcher`) only gives us a title/description string. When
+//! the `[retroachievements]` config table supplies a username and API key, this module
+//! fetches the achievement's real badge name via the same API RetroArch's
+//! own `cheevos_client` uses, downloads the badge PNG from
+//! `media.retroachievements.org` (caching it to disk so a replayed session
+//! doesn't re-fetch it), and hands the raw bytes back for `main` to attach
+//! to the already-queued popup. Runs on its own thread; a failed or slow
+//! fetch just means the popup stays text-only — never blocks popups.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, Sender};
+
+use log::{debug, warn};
+use serde::Deserialize;
+
+use crate::config::RetroAchievementsConfig;
+
+const API_BASE: &str = "https://retroachievements.org/API";
+const MEDIA_BASE: &str = "https://media.retroachievements.org/Badge";
+
+#[derive(Debug, Deserialize)]
+struct AchievementResponse {
+    #[serde(rename = "BadgeName")]
+    badge_name: Option<String>,
+}
+
+/// Spawn the API client thread. Reads achievement ids from `id_rx` (sent by
+/// `main` for each scraped `Unlock` event) and sends back `(id, badge_png)`
+/// on `tx` once the badge is fetched. Runs until `id_rx` disconnects.
+pub fn spawn(cfg: RetroAchievementsConfig, cache_dir: PathBuf, id_rx: Receiver<String>, tx: Sender<(String, Vec<u8>)>) {
+    std::thread::spawn(move || {
+        if let Err(e) = fs::create_dir_all(&cache_dir) {
+            warn!("Failed to create badge cache dir {}: {e}", cache_dir.display());
+        }
+        for id in id_rx {
+            match fetch_badge_for_achievement(&cfg, &cache_dir, &id) {
+                Ok(badge_png) => {
+                    if tx.send((id, badge_png)).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => debug!("RetroAchievements badge fetch for {id} failed: {e}"),
+            }
+        }
+    });
+}
+
+/// Look up the achievement's badge name via the Web API, then fetch (or
+/// read from cache) the badge image itself.
+fn fetch_badge_for_achievement(cfg: &RetroAchievementsConfig, cache_dir: &Path, id: &str) -> Result<Vec<u8>, String> {
+    let (Some(username), Some(api_key)) = (&cfg.username, &cfg.api_key) else {
+        return Err("RetroAchievements API not configured".into());
+    };
+
+    let url = format!("{API_BASE}/API_GetAchievementUnlocks.php?z={username}&y={api_key}&a={id}");
+    let response: AchievementResponse = ureq::get(&url)
+        .call()
+        .map_err(|e| format!("request: {e}"))?
+        .into_json()
+        .map_err(|e| format!("decode: {e}"))?;
+
+    let badge_name = response.badge_name.ok_or("no BadgeName in response")?;
+    fetch_badge(cache_dir, &badge_name)
+}
+
+/// Download (or read from the on-disk cache) the raw PNG bytes for a badge.
+fn fetch_badge(cache_dir: &Path, badge_name: &str) -> Result<Vec<u8>, String> {
+    let cache_path = cache_dir.join(format!("{badge_name}.png"));
+    if let Ok(bytes) = fs::read(&cache_path) {
+        return Ok(bytes);
+    }
+
+    let url = format!("{MEDIA_BASE}/{badge_name}.png");
+    let mut reader = ureq::get(&url).call().map_err(|e| format!("request: {e}"))?.into_reader();
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).map_err(|e| format!("read: {e}"))?;
+
+    if let Err(e) = fs::write(&cache_path, &bytes) {
+        debug!("Failed to cache badge {}: {e}", cache_path.display());
+    }
+    Ok(bytes)
+}
@@ -4,11 +4,15 @@
 //! Premium console aesthetic: layered shadows, gradient panels, glow accents,
 //! bold selection pills, controller face buttons.
 
-use crate::config::MenuConfig;
+use crate::bidi;
+use crate::config::{MenuConfig, MenuEntry, TextFitConfig};
+use crate::glyph_cache::{GlyphAtlas, GlyphInfo};
 use crate::menu::{Menu, MenuState};
-use crate::popup::Popup;
-use crate::theme::Theme;
+use crate::popup::{Popup, PopupQueue};
+use crate::theme::{ColorRole, FontFamily, FontStyle, FontStyles, Theme};
+use std::cell::RefCell;
 use tiny_skia::*;
+use unicode_segmentation::UnicodeSegmentation;
 
 // ── Layout constants ────────────────────────────────────────
 
@@ -20,6 +24,7 @@ const TOAST_MARGIN: f32 = 28.0;
 const TOAST_BADGE_SIZE: f32 = 60.0;
 const TOAST_BADGE_RADIUS: f32 = 12.0;
 const TOAST_BADGE_PAD: f32 = 18.0;
+const TOAST_STACK_GAP: f32 = 12.0;
 
 // Menu panel (left side)
 const MENU_WIDTH: f32 = 320.0;
@@ -30,6 +35,16 @@ const MENU_HINT_H: f32 = 44.0;
 const MENU_PAD: f32 = 16.0;
 const MENU_ITEM_INSET: f32 = 10.0;
 const MENU_SEL_RADIUS: f32 = 12.0;
+const MENU_HEADER_H: f32 = 34.0;
+const SEARCH_BOX_H: f32 = 38.0;
+const SEARCH_BOX_RADIUS: f32 = 10.0;
+const SEARCH_BOX_GAP: f32 = 10.0;
+
+// Quick-settings controls (toggle / option cycler / slider)
+const TOGGLE_W: f32 = 40.0;
+const TOGGLE_H: f32 = 20.0;
+const SLIDER_W: f32 = 90.0;
+const SLIDER_H: f32 = 8.0;
 
 // Status pill (top-left)
 const STATUS_H: f32 = 32.0;
@@ -37,9 +52,30 @@ const STATUS_RADIUS: f32 = 16.0;
 const STATUS_MARGIN: f32 = 28.0;
 const STATUS_PAD_H: f32 = 16.0;
 
+// Leaderboard tracker stack (bottom-right, rendered standalone like a toast)
+const TRACKER_W: f32 = 220.0;
+const TRACKER_ROW_H: f32 = 34.0;
+const TRACKER_GAP: f32 = 6.0;
+const TRACKER_RADIUS: f32 = 10.0;
+const TRACKER_PAD_H: f32 = 12.0;
+const TRACKER_MARGIN: f32 = 20.0;
+
 // Backdrop
 const BACKDROP_ALPHA: f32 = 0.45;
 
+/// Screen height every layout constant above is tuned against. `device_scale`
+/// maps the current `screen_h` back onto this baseline so the overlay reads
+/// the same proportional size on a 720p handheld and a 4K TV.
+const UI_SCALE_BASELINE_H: f32 = 1080.0;
+
+/// Global device-scale factor: `menu.ui_scale` if the theme sets one,
+/// otherwise `screen_h` scaled against `UI_SCALE_BASELINE_H`. This is
+/// distinct from `Menu::scale()`, which is the menu's own open/close pop
+/// animation — the two multiply together when drawing the menu panel.
+fn device_scale(screen_h: u32, override_scale: Option<f32>) -> f32 {
+    override_scale.unwrap_or(screen_h as f32 / UI_SCALE_BASELINE_H)
+}
+
 // Shadow layers — offset, spread, opacity
 const SHADOW_LAYERS: [(f32, f32, u8); 3] = [
     (0.0, 32.0, 30),  // ambient
@@ -57,8 +93,12 @@ pub struct Renderer {
     shadow: Color8,
     subtle: Color8,
     display_font: fontdue::Font,
+    display_atlas: RefCell<GlyphAtlas>,
     body_font: fontdue::Font,
+    body_atlas: RefCell<GlyphAtlas>,
     light_font: fontdue::Font,
+    light_atlas: RefCell<GlyphAtlas>,
+    styles: FontStyles,
 }
 
 #[derive(Clone, Copy)]
@@ -91,9 +131,10 @@ impl Color8 {
 
 /// All state needed to render one frame.
 pub struct FrameState<'a> {
-    pub popup: Option<&'a Popup>,
+    pub popup: &'a PopupQueue,
     pub menu: Option<&'a Menu>,
     pub menu_config: &'a MenuConfig,
+    pub text_fit: &'a TextFitConfig,
     pub game_name: Option<&'a str>,
 }
 
@@ -109,8 +150,134 @@ impl Renderer {
             shadow: Color8::from_theme(&theme.shadow_color),
             subtle: Color8::from_theme(&theme.subtle_color),
             display_font: load_font(&theme.font_display_path),
+            display_atlas: RefCell::new(GlyphAtlas::new()),
             body_font: load_font(&theme.font_path),
+            body_atlas: RefCell::new(GlyphAtlas::new()),
             light_font: load_font(&theme.font_light_path),
+            light_atlas: RefCell::new(GlyphAtlas::new()),
+            styles: theme.font_styles,
+        }
+    }
+
+    // ── Named font-style registry ───────────────────────────
+
+    fn font_for(&self, family: FontFamily) -> &fontdue::Font {
+        match family {
+            FontFamily::Display => &self.display_font,
+            FontFamily::Body => &self.body_font,
+            FontFamily::Light => &self.light_font,
+        }
+    }
+
+    fn atlas_for(&self, family: FontFamily) -> &RefCell<GlyphAtlas> {
+        match family {
+            FontFamily::Display => &self.display_atlas,
+            FontFamily::Body => &self.body_atlas,
+            FontFamily::Light => &self.light_atlas,
+        }
+    }
+
+    fn color_for(&self, role: ColorRole) -> Color8 {
+        match role {
+            ColorRole::Fg => self.fg,
+            ColorRole::Accent => self.accent,
+            ColorRole::Subtle => self.subtle,
+        }
+    }
+
+    /// Draw `text` in a named `style`, scaled by `scale`. `c` supplies the
+    /// alpha to draw with (typically `self.color_for(style.color).with_alpha(oa(N))`);
+    /// its r/g/b are used as-is, so pass `self.color_for(style.color)` unless
+    /// the call site intentionally wants a different base color (e.g. a
+    /// selected menu item swapping to `onMainColor`). Honors
+    /// `style.letter_spacing` by drawing char-by-char when nonzero.
+    fn draw_styled_text(&self, pixmap: &mut Pixmap, text: &str, style: &FontStyle, scale: f32, x: f32, y: f32, c: Color8) {
+        let font = self.font_for(style.family);
+        let atlas = self.atlas_for(style.family);
+        let size = style.size * scale;
+        if style.letter_spacing == 0.0 {
+            rasterize_text(pixmap, text, font, atlas, size, x, y, c);
+            return;
+        }
+        let spacing = style.letter_spacing * scale;
+        let mut cursor_x = x;
+        for ch in text.chars() {
+            let s = ch.to_string();
+            rasterize_text(pixmap, &s, font, atlas, size, cursor_x, y, c);
+            cursor_x += measure_text(font, atlas, &s, size) + spacing;
+        }
+    }
+
+    /// Bidi-aware version of `draw_styled_text`, for dynamic/user-supplied
+    /// text (game names, achievement titles, menu labels) that might be
+    /// RTL script. Runs in visual order through `draw_styled_text` per run,
+    /// so `style.letter_spacing` still applies within each run.
+    fn draw_styled_text_bidi(&self, pixmap: &mut Pixmap, text: &str, style: &FontStyle, scale: f32, x: f32, y: f32, c: Color8, direction: bidi::Direction) {
+        let font = self.font_for(style.family);
+        let atlas = self.atlas_for(style.family);
+        let size = style.size * scale;
+        let mut cursor_x = x;
+        for (run, _rtl) in bidi::visual_runs(text, direction) {
+            self.draw_styled_text(pixmap, &run, style, scale, cursor_x, y, c);
+            cursor_x += measure_text(font, atlas, &run, size);
+        }
+    }
+
+    /// Bidi-aware version of `draw_styled_text` with an outline/halo (see
+    /// `rasterize_text_outlined`). `outline_width` of `0.0` behaves exactly
+    /// like `draw_styled_text_bidi` with no outline. Letter-spacing isn't
+    /// honored here — nothing currently pairs tracked-out labels with an
+    /// outline, so it's left unsupported rather than threaded through
+    /// unused.
+    fn draw_styled_text_bidi_outlined(&self, pixmap: &mut Pixmap, text: &str, style: &FontStyle, scale: f32, x: f32, y: f32, c: Color8, outline_width: f32, outline_color: Color8, direction: bidi::Direction) {
+        let font = self.font_for(style.family);
+        let atlas = self.atlas_for(style.family);
+        let size = style.size * scale;
+        let mut cursor_x = x;
+        for (run, _rtl) in bidi::visual_runs(text, direction) {
+            rasterize_text_outlined(pixmap, &run, font, atlas, size, cursor_x, y, c, outline_width, outline_color);
+            cursor_x += measure_text(font, atlas, &run, size);
+        }
+    }
+
+    /// Draw bidi-reordered text that doesn't necessarily fit `box_w`: fits
+    /// comfortably or overflows by less than `text_fit.marquee_threshold`
+    /// draws exactly like `draw_styled_text_bidi[_outlined]` (ellipsis-
+    /// truncated via `truncate_to_width_bidi`, positioned per `align`);
+    /// overflowing by more marquee-scrolls the full text left over time,
+    /// clipped to `[box_x, box_x + box_w)` (see `fit_text`). `align` only
+    /// applies to the fitting/truncated case — a scrolling line has no
+    /// fixed center to align to, so it always starts flush with `box_x`.
+    /// The marquee pass drops `outline_width`/`outline_color` in favor of a
+    /// plain clipped fill; combining halo dilation with scroll-clipping
+    /// isn't worth the complexity for what's meant to be a rare overflow.
+    fn draw_fitted_bidi(
+        &self, pixmap: &mut Pixmap, text: &str, style: &FontStyle, scale: f32,
+        box_x: f32, box_w: f32, y: f32, c: Color8, direction: bidi::Direction, align: Align,
+        outline_width: f32, outline_color: Color8, text_fit: &TextFitConfig, elapsed_ms: u64,
+    ) {
+        let font = self.font_for(style.family);
+        let atlas = self.atlas_for(style.family);
+        let size = style.size * scale;
+        let text_w = measure_text_bidi(font, atlas, text, size, direction);
+        match fit_text(text_w, box_w, text_fit, elapsed_ms) {
+            TextFit::Static => {
+                let trunc = truncate_to_width_bidi(font, atlas, text, size, box_w, direction);
+                let trunc_w = measure_text_bidi(font, atlas, &trunc, size, direction);
+                let x = match align {
+                    Align::Center => box_x + (box_w - trunc_w) / 2.0,
+                    Align::Right => box_x + box_w - trunc_w,
+                    Align::Left => box_x,
+                };
+                self.draw_styled_text_bidi_outlined(pixmap, &trunc, style, scale, x, y, c, outline_width, outline_color, direction);
+            }
+            TextFit::Marquee { scroll_x } => {
+                let mut cursor_x = box_x - scroll_x;
+                for (run, _rtl) in bidi::visual_runs(text, direction) {
+                    rasterize_text_clipped(pixmap, &run, font, atlas, size, cursor_x, y, c, box_x, box_x + box_w);
+                    cursor_x += measure_text(font, atlas, &run, size);
+                }
+            }
         }
     }
 
@@ -121,6 +288,8 @@ impl Renderer {
         let mut pixmap = Pixmap::new(screen_w, screen_h).expect("pixmap");
         pixmap.fill(tiny_skia::Color::TRANSPARENT);
 
+        let scale = device_scale(screen_h, state.menu_config.ui_scale);
+
         let menu_visible = state.menu.map_or(false, |m| m.is_visible());
         let menu_opacity = state.menu.map_or(0.0, |m| m.opacity());
 
@@ -131,19 +300,21 @@ impl Renderer {
 
         // Status pill (top-left, only when menu is open)
         if menu_visible {
-            self.draw_status_pill(&mut pixmap, state.game_name, menu_opacity);
+            self.draw_status_pill(&mut pixmap, state.game_name, menu_opacity, scale);
         }
 
         // Quick menu (left side)
         if let Some(menu) = state.menu {
             if menu.is_visible() {
-                self.draw_menu_panel(&mut pixmap, menu, state.menu_config, screen_h);
+                self.draw_menu_panel(&mut pixmap, menu, state.menu_config, screen_h, scale, state.text_fit);
             }
         }
 
-        // Achievement toast (top-right, slides from right)
-        if let Some(popup) = state.popup {
-            self.draw_achievement_toast(&mut pixmap, popup, screen_w);
+        // Achievement toasts (top-right, slides from right, stacked downward)
+        let mut toast_y = TOAST_MARGIN * scale;
+        for popup in state.popup.visible() {
+            self.draw_achievement_toast(&mut pixmap, popup, screen_w, toast_y, scale, state.text_fit);
+            toast_y += (TOAST_H + TOAST_STACK_GAP) * scale;
         }
 
         pixmap_to_argb(&pixmap)
@@ -151,23 +322,45 @@ impl Renderer {
 
     // ── Legacy API ──────────────────────────────────────────
 
-    pub fn render_popup(&self, title: &str, description: &str, opacity: f32) -> Vec<u32> {
+    pub fn render_popup(&self, title: &str, description: &str, opacity: f32, text_fit: &TextFitConfig, elapsed_ms: u64) -> Vec<u32> {
         let w = TOAST_W as u32 + TOAST_MARGIN as u32 * 2;
         let h = TOAST_H as u32 + TOAST_MARGIN as u32 * 2;
         let mut pixmap = Pixmap::new(w, h).expect("pixmap");
         pixmap.fill(tiny_skia::Color::TRANSPARENT);
 
         let popup = Popup::new(title.to_string(), description.to_string());
-        self.draw_toast_at(&mut pixmap, TOAST_MARGIN, TOAST_MARGIN, &popup, opacity, 0.0);
+        self.draw_toast_at(&mut pixmap, TOAST_MARGIN, TOAST_MARGIN, &popup, opacity, 0.0, 1.0, text_fit, elapsed_ms);
         pixmap_to_argb(&pixmap)
     }
 
-    pub fn render_menu(&self, menu: &Menu, screen_w: u32, screen_h: u32, config: &MenuConfig) -> Vec<u32> {
+    /// Render the leaderboard tracker stack — one row per active attempt, in
+    /// the order given (`TrackerSet::rows` order). Returns the composited
+    /// pixels plus their actual width/height, since the stack grows with
+    /// `rows.len()` rather than fitting a single fixed-size buffer like
+    /// `render_popup`. Callers composite into the bottom-right corner.
+    pub fn render_trackers(&self, rows: &[(String, String)]) -> (Vec<u32>, u32, u32) {
+        let w = TRACKER_W as u32;
+        let h = (rows.len() as f32 * TRACKER_ROW_H + (rows.len().saturating_sub(1)) as f32 * TRACKER_GAP) as u32;
+        let mut pixmap = Pixmap::new(w.max(1), h.max(1)).expect("pixmap");
+        pixmap.fill(tiny_skia::Color::TRANSPARENT);
+
+        let mut y = 0.0;
+        for (name, value) in rows {
+            self.draw_tracker_row(&mut pixmap, y, name, value);
+            y += TRACKER_ROW_H + TRACKER_GAP;
+        }
+
+        (pixmap_to_argb(&pixmap), w, h)
+    }
+
+    pub fn render_menu(&self, menu: &Menu, screen_w: u32, screen_h: u32, config: &MenuConfig, text_fit: &TextFitConfig, game_name: Option<&str>) -> Vec<u32> {
+        let empty_queue = PopupQueue::new();
         let state = FrameState {
-            popup: None,
+            popup: &empty_queue,
             menu: Some(menu),
             menu_config: config,
-            game_name: Some("Preview Game"),
+            text_fit,
+            game_name,
         };
         self.render_frame(&state, screen_w, screen_h)
     }
@@ -195,57 +388,59 @@ impl Renderer {
 
     // ── Achievement toast ───────────────────────────────────
 
-    fn draw_achievement_toast(&self, pixmap: &mut Pixmap, popup: &Popup, screen_w: u32) {
+    fn draw_achievement_toast(&self, pixmap: &mut Pixmap, popup: &Popup, screen_w: u32, y: f32, scale: f32, text_fit: &TextFitConfig) {
         let opacity = popup.opacity();
         if opacity <= 0.0 { return; }
 
         let slide = popup.slide_offset();
-        let x = screen_w as f32 - TOAST_W - TOAST_MARGIN + (TOAST_W + TOAST_MARGIN) * slide;
-        let y = TOAST_MARGIN;
+        let (toast_w, toast_margin) = (TOAST_W * scale, TOAST_MARGIN * scale);
+        let x = screen_w as f32 - toast_w - toast_margin + (toast_w + toast_margin) * slide;
 
-        self.draw_toast_at(pixmap, x, y, popup, opacity, slide);
+        self.draw_toast_at(pixmap, x, y, popup, opacity, slide, scale, text_fit, popup.elapsed_ms());
     }
 
-    fn draw_toast_at(&self, pixmap: &mut Pixmap, x: f32, y: f32, popup: &Popup, opacity: f32, _slide: f32) {
+    fn draw_toast_at(&self, pixmap: &mut Pixmap, x: f32, y: f32, popup: &Popup, opacity: f32, _slide: f32, scale: f32, text_fit: &TextFitConfig, elapsed_ms: u64) {
         let oa = |base: u8| -> u8 { (base as f32 * opacity) as u8 };
+        let (toast_w, toast_h, toast_radius) = (TOAST_W * scale, TOAST_H * scale, TOAST_RADIUS * scale);
+        let (badge_size, badge_radius, badge_pad) = (TOAST_BADGE_SIZE * scale, TOAST_BADGE_RADIUS * scale, TOAST_BADGE_PAD * scale);
 
         // Drop shadows (3 layers for depth) — use theme shadow color
         for &(offset, spread, alpha) in &SHADOW_LAYERS {
             let sa = oa(alpha);
             if sa == 0 { continue; }
-            let s = spread / 2.0;
+            let s = spread * scale / 2.0;
             draw_rounded_rect(pixmap,
-                x - s + offset, y - s + offset * 1.5,
-                TOAST_W + spread, TOAST_H + spread,
-                TOAST_RADIUS + s,
+                x - s + offset * scale, y - s + offset * scale * 1.5,
+                toast_w + spread * scale, toast_h + spread * scale,
+                toast_radius + s,
                 self.shadow.with_alpha(sa));
         }
 
         // Panel background gradient — card color, lighter at top via subtle
         let bg_top = self.card.blend(self.subtle, 0.06).with_alpha(oa(235));
         let bg_bot = self.card.with_alpha(oa(245));
-        draw_gradient_rounded_rect(pixmap, x, y, TOAST_W, TOAST_H, TOAST_RADIUS, bg_top, bg_bot);
+        draw_gradient_rounded_rect(pixmap, x, y, toast_w, toast_h, toast_radius, bg_top, bg_bot);
 
         // Outer border — subtle edge definition
-        draw_rounded_rect_stroke(pixmap, x, y, TOAST_W, TOAST_H, TOAST_RADIUS,
+        draw_rounded_rect_stroke(pixmap, x, y, toast_w, toast_h, toast_radius,
             self.subtle.with_alpha(oa(8)));
 
         // Top inner highlight
-        draw_rounded_rect(pixmap, x + 1.0, y + 1.0, TOAST_W - 2.0, 1.0, TOAST_RADIUS - 1.0,
+        draw_rounded_rect(pixmap, x + scale, y + scale, toast_w - 2.0 * scale, scale, toast_radius - scale,
             self.subtle.with_alpha(oa(15)));
 
         // Left accent glow (bleed + solid stripe)
-        draw_rounded_rect(pixmap, x + 1.0, y + 12.0, 8.0, TOAST_H - 24.0, 4.0,
+        draw_rounded_rect(pixmap, x + scale, y + 12.0 * scale, 8.0 * scale, toast_h - 24.0 * scale, 4.0 * scale,
             self.accent.with_alpha(oa(15)));
-        draw_rounded_rect(pixmap, x + 2.0, y + 14.0, 3.0, TOAST_H - 28.0, 1.5,
+        draw_rounded_rect(pixmap, x + 2.0 * scale, y + 14.0 * scale, 3.0 * scale, toast_h - 28.0 * scale, 1.5 * scale,
             self.accent.with_alpha(oa(220)));
 
         // Badge area
-        let badge_x = x + TOAST_BADGE_PAD;
-        let badge_y = y + (TOAST_H - TOAST_BADGE_SIZE) / 2.0;
+        let badge_x = x + badge_pad;
+        let badge_y = y + (toast_h - badge_size) / 2.0;
 
         let has_badge = if let Some(ref png_bytes) = popup.badge_png {
-            self.blit_badge(pixmap, badge_x, badge_y, TOAST_BADGE_SIZE, TOAST_BADGE_RADIUS, png_bytes, opacity)
+            self.blit_badge(pixmap, badge_x, badge_y, badge_size, badge_radius, png_bytes, opacity)
         } else {
             false
         };
@@ -253,66 +448,91 @@ impl Renderer {
         if !has_badge {
             // Shadow behind badge
             draw_rounded_rect(pixmap,
-                badge_x + 2.0, badge_y + 3.0,
-                TOAST_BADGE_SIZE, TOAST_BADGE_SIZE, TOAST_BADGE_RADIUS,
+                badge_x + 2.0 * scale, badge_y + 3.0 * scale,
+                badge_size, badge_size, badge_radius,
                 self.shadow.with_alpha(oa(50)));
             // Badge bg — accent gradient darkened with shadow color
             let badge_top = self.accent.with_alpha(oa(220));
             let badge_bot = self.accent.blend(self.shadow, 0.25).with_alpha(oa(220));
             draw_gradient_rounded_rect(pixmap,
-                badge_x, badge_y, TOAST_BADGE_SIZE, TOAST_BADGE_SIZE,
-                TOAST_BADGE_RADIUS, badge_top, badge_bot);
+                badge_x, badge_y, badge_size, badge_size,
+                badge_radius, badge_top, badge_bot);
             // Badge inner highlight
             draw_rounded_rect(pixmap,
-                badge_x + 1.0, badge_y + 1.0,
-                TOAST_BADGE_SIZE - 2.0, TOAST_BADGE_SIZE * 0.4,
-                TOAST_BADGE_RADIUS - 1.0,
+                badge_x + scale, badge_y + scale,
+                badge_size - 2.0 * scale, badge_size * 0.4,
+                badge_radius - scale,
                 self.subtle.with_alpha(oa(30)));
             // Star centered in badge
-            let star_size = 24.0;
-            let sx = badge_x + (TOAST_BADGE_SIZE - measure_text(&self.display_font, "\u{2605}", star_size)) / 2.0;
-            let sy = text_center_y(&self.display_font, star_size, badge_y, TOAST_BADGE_SIZE);
-            rasterize_text(pixmap, "\u{2605}", &self.display_font, star_size,
+            let star_size = 24.0 * scale;
+            let sx = badge_x + (badge_size - measure_text(&self.display_font, &self.display_atlas, "\u{2605}", star_size)) / 2.0;
+            let sy = text_center_y(&self.display_font, star_size, badge_y, badge_size);
+            rasterize_text(pixmap, "\u{2605}", &self.display_font, &self.display_atlas, star_size,
                 sx, sy, self.on_accent.with_alpha(oa(240)));
         }
 
         // Badge border ring
         draw_rounded_rect_stroke(pixmap,
-            badge_x, badge_y, TOAST_BADGE_SIZE, TOAST_BADGE_SIZE,
-            TOAST_BADGE_RADIUS, self.accent.with_alpha(oa(60)));
+            badge_x, badge_y, badge_size, badge_size,
+            badge_radius, self.accent.with_alpha(oa(60)));
 
         // Text column — 3 lines vertically distributed in toast
-        let text_x = badge_x + TOAST_BADGE_SIZE + 16.0;
-        let text_max_w = TOAST_W - (text_x - x) - 16.0;
-
-        // Vertical layout: header(9.5) + title(16) + desc(11.5) with gaps
-        let header_size = 9.5_f32;
-        let title_size = 16.0_f32;
-        let desc_size = 11.5_f32;
-        let header_h = text_height(&self.body_font, header_size);
-        let title_h = text_height(&self.display_font, title_size);
-        let desc_h = text_height(&self.light_font, desc_size);
-        let line_gap = 2.0;
+        let text_x = badge_x + badge_size + 16.0 * scale;
+        let text_max_w = toast_w - (text_x - x) - 16.0 * scale;
+
+        // Vertical layout: header + title + desc, sizes from the font-style registry
+        let header_style = self.styles.toast_header;
+        let title_style = self.styles.toast_title;
+        let desc_style = self.styles.toast_desc;
+        let header_size = header_style.size * scale;
+        let title_size = title_style.size * scale;
+        let desc_size = desc_style.size * scale;
+        let header_h = text_height(self.font_for(header_style.family), header_size);
+        let title_h = text_height(self.font_for(title_style.family), title_size);
+        let desc_h = text_height(self.font_for(desc_style.family), desc_size);
+        let line_gap = 2.0 * scale;
         let has_desc = !popup.description.is_empty();
-        let total_text_h = header_h + line_gap + title_h + if has_desc { line_gap + desc_h } else { 0.0 };
-        let text_top = y + (TOAST_H - total_text_h) / 2.0;
+        // Up to 2 lines — enough for most RA descriptions without the toast
+        // growing taller than its fixed badge/background artwork.
+        let desc_lines = if has_desc {
+            wrap_text(self.font_for(desc_style.family), self.atlas_for(desc_style.family),
+                &popup.description, desc_size, text_max_w, Align::Left)
+        } else {
+            Vec::new()
+        };
+        let desc_line_count = desc_lines.len().min(2);
+        let desc_block_h = if desc_line_count > 0 {
+            desc_line_count as f32 * desc_h + (desc_line_count - 1) as f32 * line_gap
+        } else {
+            0.0
+        };
+        let total_text_h = header_h + line_gap + title_h + if has_desc { line_gap + desc_block_h } else { 0.0 };
+        let text_top = y + (toast_h - total_text_h) / 2.0;
 
         // "ACHIEVEMENT UNLOCKED" header
-        rasterize_text(pixmap, "ACHIEVEMENT UNLOCKED", &self.body_font, header_size,
-            text_x, text_top, self.accent.with_alpha(oa(200)));
-
-        // Title
+        self.draw_styled_text(pixmap, "ACHIEVEMENT UNLOCKED", &header_style, scale,
+            text_x, text_top, self.color_for(header_style.color).with_alpha(oa(200)));
+
+        // Title — achievement text is user/RA content, so it's run through
+        // bidi reordering rather than assumed LTR. Haloed with the shadow
+        // color: it's the largest, highest-contrast-need line and sits
+        // directly over the panel's gradient, where a flat fill is most
+        // likely to wash out against a busy badge/background combo.
         let title_y = text_top + header_h + line_gap;
-        let title_trunc = truncate_to_width(&self.display_font, &popup.title, title_size, text_max_w);
-        rasterize_text(pixmap, &title_trunc, &self.display_font, title_size,
-            text_x, title_y, self.fg.with_alpha(oa(250)));
-
-        // Description
-        if has_desc {
+        self.draw_fitted_bidi(pixmap, &popup.title, &title_style, scale,
+            text_x, text_max_w, title_y, self.color_for(title_style.color).with_alpha(oa(250)),
+            bidi::Direction::Auto, Align::Left,
+            scale, self.shadow.with_alpha(oa(160)), text_fit, elapsed_ms);
+
+        // Description — wrapped to up to 2 lines so longer achievement text
+        // doesn't get truncated down to a single fragment.
+        if desc_line_count > 0 {
             let desc_y = title_y + title_h + line_gap;
-            let desc_trunc = truncate_to_width(&self.light_font, &popup.description, desc_size, text_max_w);
-            rasterize_text(pixmap, &desc_trunc, &self.light_font, desc_size,
-                text_x, desc_y, self.subtle.with_alpha(oa(120)));
+            for (i, line) in desc_lines.iter().take(desc_line_count).enumerate() {
+                let line_y = desc_y + i as f32 * (desc_h + line_gap);
+                self.draw_styled_text_bidi(pixmap, &line.text, &desc_style, scale,
+                    text_x + line.x_offset, line_y, self.color_for(desc_style.color).with_alpha(oa(120)), bidi::Direction::Auto);
+            }
         }
     }
 
@@ -376,21 +596,24 @@ impl Renderer {
 
     // ── Quick menu panel ────────────────────────────────────
 
-    fn draw_menu_panel(&self, pixmap: &mut Pixmap, menu: &Menu, config: &MenuConfig, screen_h: u32) {
+    fn draw_menu_panel(&self, pixmap: &mut Pixmap, menu: &Menu, config: &MenuConfig, screen_h: u32, device_scale: f32, text_fit: &TextFitConfig) {
         let opacity = menu.opacity();
         if opacity <= 0.0 { return; }
         let oa = |base: u8| -> u8 { (base as f32 * opacity) as u8 };
-        let scale = menu.scale();
+        let marquee_elapsed_ms = menu.marquee_elapsed_ms();
+        // Open/close pop animation composed with the global device scale.
+        let scale = menu.scale() * device_scale;
 
-        let n_items = menu.items().len() as f32;
         let top_pad = 20.0;
-        let panel_h = top_pad + n_items * MENU_ITEM_H + MENU_HINT_H + MENU_PAD * 2.0;
+        let visible_items = menu.visible_items();
+        let content_h: f32 = visible_items.iter().map(|(_, it)| entry_height(it)).sum();
+        let panel_h = top_pad + SEARCH_BOX_H + SEARCH_BOX_GAP + content_h + MENU_HINT_H + MENU_PAD * 2.0;
         let panel_w = MENU_WIDTH * scale;
         let panel_h_scaled = panel_h * scale;
 
         // Slide from left
         let slide_t = (1.0 - opacity).max(0.0);
-        let panel_x = MENU_MARGIN - (MENU_WIDTH * 0.3 * slide_t);
+        let panel_x = MENU_MARGIN * device_scale - (MENU_WIDTH * scale * 0.3 * slide_t);
         let panel_y = (screen_h as f32 - panel_h_scaled) / 2.0;
 
         // Drop shadows — use theme shadow color
@@ -421,80 +644,173 @@ impl Renderer {
             panel_w - 2.0, 1.0, MENU_RADIUS * scale - 1.0,
             self.subtle.with_alpha(oa(12)));
 
+        // Search/filter box
+        let search_y = panel_y + top_pad * scale + MENU_PAD * scale;
+        let search_h = SEARCH_BOX_H * scale;
+        let search_x = panel_x + MENU_PAD * scale;
+        let search_w = panel_w - MENU_PAD * scale * 2.0;
+        let search_text_size = 14.0 * scale;
+        let search_pad = 10.0 * scale;
+
+        draw_rounded_rect(pixmap, search_x, search_y, search_w, search_h, SEARCH_BOX_RADIUS * scale,
+            self.subtle.with_alpha(oa(30)));
+        draw_rounded_rect_stroke(pixmap, search_x, search_y, search_w, search_h, SEARCH_BOX_RADIUS * scale,
+            self.accent.with_alpha(oa(60)));
+
+        let query = menu.search_query();
+        let search_text_y = text_center_y(&self.body_font, search_text_size, search_y, search_h);
+        if query.is_empty() {
+            rasterize_text(pixmap, "Search\u{2026}", &self.body_font, &self.body_atlas, search_text_size,
+                search_x + search_pad, search_text_y, self.subtle.with_alpha(oa(140)));
+        } else {
+            rasterize_text(pixmap, query, &self.body_font, &self.body_atlas, search_text_size,
+                search_x + search_pad, search_text_y, self.fg.with_alpha(oa(230)));
+        }
+
+        // Blinking caret — a thin accent bar positioned after the nth typed char.
+        let caret_prefix: String = query.chars().take(menu.search_caret()).collect();
+        let caret_x = search_x + search_pad + measure_text(&self.body_font, &self.body_atlas, &caret_prefix, search_text_size);
+        let caret_alpha = oa((menu.search_caret_alpha() * 255.0) as u8);
+        fill_rect(pixmap, caret_x, search_y + search_h * 0.22, scale.max(1.0), search_h * 0.56,
+            self.accent.with_alpha(caret_alpha));
+
         // Menu items (no header text — the panel IS the menu)
-        let items_y = panel_y + top_pad * scale + MENU_PAD * scale;
-        let item_h = MENU_ITEM_H * scale;
-        let item_text_size = 15.0 * scale;
+        let items_y = search_y + search_h + SEARCH_BOX_GAP * scale;
+        let item_text_size = self.styles.menu_item.size * scale;
         let cursor = menu.cursor();
-        let is_confirming = matches!(menu.state(), MenuState::Confirming { .. });
-
-        for (i, item) in menu.items().iter().enumerate() {
-            let iy = items_y + i as f32 * item_h;
-            let is_selected = i == cursor;
-
-            if is_selected {
-                // Selected: full accent pill
-                let sel_x = panel_x + MENU_ITEM_INSET * scale;
-                let sel_w = panel_w - MENU_ITEM_INSET * scale * 2.0;
-                let sel_y = iy + 3.0 * scale;
-                let sel_h = item_h - 6.0 * scale;
-
-                // Glow behind selection
-                draw_rounded_rect(pixmap,
-                    sel_x - 2.0, sel_y - 1.0, sel_w + 4.0, sel_h + 2.0,
-                    MENU_SEL_RADIUS * scale + 2.0,
-                    self.accent.with_alpha(oa(25)));
-
-                // Selection pill — accent gradient, darkened with shadow
-                let pill_top = self.accent.with_alpha(oa(200));
-                let pill_bot = self.accent.blend(self.shadow, 0.2).with_alpha(oa(200));
-                draw_gradient_rounded_rect(pixmap,
-                    sel_x, sel_y, sel_w, sel_h,
-                    MENU_SEL_RADIUS * scale, pill_top, pill_bot);
-
-                // Inner highlight on pill
-                draw_rounded_rect(pixmap,
-                    sel_x + 1.0, sel_y + 1.0,
-                    sel_w - 2.0, sel_h * 0.35,
-                    MENU_SEL_RADIUS * scale - 1.0,
-                    self.subtle.with_alpha(oa(18)));
-
-                let label = if is_confirming {
-                    "Press again to confirm"
-                } else {
-                    &item.label
-                };
-                let text_color = if is_confirming {
-                    self.subtle.blend(self.accent, 0.3).with_alpha(oa(255))
-                } else {
-                    self.on_accent.with_alpha(oa(255))
-                };
-                let text_y = text_center_y(&self.body_font, item_text_size, iy, item_h);
-                // Center text in pill
-                let tw = measure_text(&self.body_font, label, item_text_size);
-                let tx = sel_x + (sel_w - tw) / 2.0;
-                rasterize_text(pixmap, label, &self.body_font, item_text_size,
-                    tx, text_y, text_color);
-            } else {
-                let text_y = text_center_y(&self.body_font, item_text_size, iy, item_h);
-                let tw = measure_text(&self.body_font, &item.label, item_text_size);
-                let tx = panel_x + (panel_w - tw) / 2.0;
-                rasterize_text(pixmap, &item.label, &self.body_font, item_text_size,
-                    tx, text_y, self.fg.with_alpha(oa(140)));
+        let menu_state = menu.state();
+        let is_confirming = matches!(menu_state, MenuState::Confirming { .. });
+        // While a `shell`/`retroarch` action is in flight (or just settled),
+        // the executed item's pill swaps its label for a status line instead
+        // of closing immediately — same treatment as the confirm prompt.
+        let status_label: Option<(usize, String)> = match &menu_state {
+            MenuState::Executing { item_idx } => Some((*item_idx, "Working\u{2026}".to_string())),
+            MenuState::Success { item_idx } => Some((*item_idx, "Done".to_string())),
+            MenuState::Error { item_idx, msg } => Some((*item_idx, msg.clone())),
+            _ => None,
+        };
+
+        let mut iy = items_y;
+        for (i, item) in visible_items {
+            let h = entry_height(item) * scale;
+
+            match item {
+                MenuEntry::Header(h_entry) => {
+                    let size = 11.0 * scale;
+                    let text_y = text_center_y(&self.body_font, size, iy, h);
+                    rasterize_text_bidi(pixmap, &h_entry.header, &self.body_font, &self.body_atlas, size,
+                        panel_x + MENU_PAD * scale, text_y, self.subtle.blend(self.fg, 0.4).with_alpha(oa(200)), bidi::Direction::Auto);
+                }
+                MenuEntry::Spacer(_) => {}
+                MenuEntry::Disabled(d_entry) => {
+                    let text_y = text_center_y(&self.body_font, item_text_size, iy, h);
+                    let tw = measure_text_bidi(&self.body_font, &self.body_atlas, &d_entry.disabled, item_text_size, bidi::Direction::Auto);
+                    let tx = panel_x + (panel_w - tw) / 2.0;
+                    rasterize_text_bidi(pixmap, &d_entry.disabled, &self.body_font, &self.body_atlas, item_text_size,
+                        tx, text_y, self.fg.with_alpha(oa(60)), bidi::Direction::Auto);
+                }
+                _ => {
+                    let is_selected = i == cursor;
+
+                    if is_selected {
+                        // Selected: full accent pill
+                        let sel_x = panel_x + MENU_ITEM_INSET * scale;
+                        let sel_w = panel_w - MENU_ITEM_INSET * scale * 2.0;
+                        let sel_y = iy + 3.0 * scale;
+                        let sel_h = h - 6.0 * scale;
+
+                        // Glow behind selection
+                        draw_rounded_rect(pixmap,
+                            sel_x - 2.0, sel_y - 1.0, sel_w + 4.0, sel_h + 2.0,
+                            MENU_SEL_RADIUS * scale + 2.0,
+                            self.accent.with_alpha(oa(25)));
+
+                        // Selection pill — accent gradient, darkened with shadow
+                        let pill_top = self.accent.with_alpha(oa(200));
+                        let pill_bot = self.accent.blend(self.shadow, 0.2).with_alpha(oa(200));
+                        draw_gradient_rounded_rect(pixmap,
+                            sel_x, sel_y, sel_w, sel_h,
+                            MENU_SEL_RADIUS * scale, pill_top, pill_bot);
+
+                        // Inner highlight on pill
+                        draw_rounded_rect(pixmap,
+                            sel_x + 1.0, sel_y + 1.0,
+                            sel_w - 2.0, sel_h * 0.35,
+                            MENU_SEL_RADIUS * scale - 1.0,
+                            self.subtle.with_alpha(oa(18)));
+
+                        let status_here = status_label.as_ref().and_then(|(idx, label)| (*idx == i).then_some(label.as_str()));
+                        if let Some(label) = status_here {
+                            let text_color = match &menu_state {
+                                MenuState::Error { .. } => self.accent.blend(self.fg, 0.5).with_alpha(oa(255)),
+                                _ => self.on_accent.with_alpha(oa(255)),
+                            };
+                            let text_y = text_center_y(&self.body_font, item_text_size, iy, h);
+                            let tw = measure_text(&self.body_font, &self.body_atlas, label, item_text_size);
+                            let tx = sel_x + (sel_w - tw) / 2.0;
+                            rasterize_text(pixmap, label, &self.body_font, &self.body_atlas, item_text_size,
+                                tx, text_y, text_color);
+                        } else if is_confirming {
+                            let label = "Press again to confirm";
+                            let text_color = self.subtle.blend(self.accent, 0.3).with_alpha(oa(255));
+                            let text_y = text_center_y(&self.body_font, item_text_size, iy, h);
+                            let tw = measure_text(&self.body_font, &self.body_atlas, label, item_text_size);
+                            let tx = sel_x + (sel_w - tw) / 2.0;
+                            rasterize_text(pixmap, label, &self.body_font, &self.body_atlas, item_text_size,
+                                tx, text_y, text_color);
+                        } else if let MenuEntry::Action(action_item) = item {
+                            // Plain actions keep their label centered in the pill.
+                            let text_color = self.on_accent.with_alpha(oa(255));
+                            let text_y = text_center_y(&self.body_font, item_text_size, iy, h);
+                            self.draw_fitted_bidi(pixmap, &action_item.label, &self.styles.menu_item, scale,
+                                sel_x, sel_w, text_y, text_color, bidi::Direction::Auto, Align::Center,
+                                0.0, self.shadow, text_fit, marquee_elapsed_ms);
+                        } else {
+                            // Quick-settings entries: label left, control right.
+                            let text_color = self.on_accent.with_alpha(oa(255));
+                            let text_y = text_center_y(&self.body_font, item_text_size, iy, h);
+                            let tx = sel_x + MENU_ITEM_INSET * scale;
+                            rasterize_text(pixmap, item.label(), &self.body_font, &self.body_atlas, item_text_size,
+                                tx, text_y, text_color);
+                            let control_right = sel_x + sel_w - MENU_ITEM_INSET * scale;
+                            let cy = iy + h / 2.0;
+                            self.draw_entry_control(pixmap, item, control_right, cy, item_text_size, scale, opacity, true);
+                        }
+                    } else {
+                        match item {
+                            MenuEntry::Action(action_item) => {
+                                let text_y = text_center_y(&self.body_font, item_text_size, iy, h);
+                                self.draw_fitted_bidi(pixmap, &action_item.label, &self.styles.menu_item, scale,
+                                    panel_x, panel_w, text_y, self.fg.with_alpha(oa(140)), bidi::Direction::Auto, Align::Center,
+                                    0.0, self.shadow, text_fit, marquee_elapsed_ms);
+                            }
+                            _ => {
+                                let text_y = text_center_y(&self.body_font, item_text_size, iy, h);
+                                let tx = panel_x + MENU_PAD * scale;
+                                rasterize_text(pixmap, item.label(), &self.body_font, &self.body_atlas, item_text_size,
+                                    tx, text_y, self.fg.with_alpha(oa(140)));
+                                let control_right = panel_x + panel_w - MENU_PAD * scale;
+                                let cy = iy + h / 2.0;
+                                self.draw_entry_control(pixmap, item, control_right, cy, item_text_size, scale, opacity, false);
+                            }
+                        }
+                    }
+                }
             }
 
             // NO divider lines — spacing alone separates items
+            iy += h;
         }
 
         // Hint bar (bottom)
-        let hint_y = items_y + n_items * item_h + 4.0 * scale;
+        let hint_y = iy + 4.0 * scale;
         // Subtle separator
         fill_rect(pixmap,
             panel_x + MENU_PAD * scale * 2.0, hint_y,
             panel_w - MENU_PAD * scale * 4.0, 1.0,
             self.subtle.with_alpha(oa(8)));
 
-        let hint_size = 10.0 * scale;
+        let hint_size = self.styles.hint.size * scale;
         let hint_center_y = hint_y + MENU_HINT_H * scale * 0.5;
 
         // Controller face buttons as circles (PS-style)
@@ -506,12 +822,58 @@ impl Renderer {
         let _ = self.draw_face_button(pixmap, hx, hint_center_y, "B", "Back", hint_size, scale, opacity, false);
     }
 
+    /// Draw the right-aligned control for a quick-settings entry (toggle
+    /// capsule, `‹ value ›` cycler, or filled slider track), ending at
+    /// `right_x`. No-op for `Action`/`Header`/`Spacer`/`Disabled` entries.
+    fn draw_entry_control(&self, pixmap: &mut Pixmap, entry: &MenuEntry, right_x: f32, cy: f32, text_size: f32, scale: f32, opacity: f32, on_pill: bool) {
+        let oa = |base: u8| -> u8 { (base as f32 * opacity) as u8 };
+        let label_color = if on_pill { self.on_accent } else { self.fg };
+
+        match entry {
+            MenuEntry::Toggle(t) => {
+                let w = TOGGLE_W * scale;
+                let h = TOGGLE_H * scale;
+                let x = right_x - w;
+                let y = cy - h / 2.0;
+                let track = if t.value {
+                    self.accent.blend(self.on_accent, if on_pill { 0.3 } else { 0.0 }).with_alpha(oa(220))
+                } else {
+                    self.subtle.with_alpha(oa(60))
+                };
+                draw_rounded_rect(pixmap, x, y, w, h, h / 2.0, track);
+                let knob_r = h / 2.0 - 2.0 * scale;
+                let knob_cx = if t.value { x + w - knob_r - 2.0 * scale } else { x + knob_r + 2.0 * scale };
+                draw_circle(pixmap, knob_cx, cy, knob_r, self.card.with_alpha(oa(255)));
+            }
+            MenuEntry::OptionCycle(c) => {
+                let value = c.options.get(c.selected).map(String::as_str).unwrap_or("");
+                let text = format!("\u{2039} {value} \u{203a}");
+                let tw = measure_text(&self.display_font, &self.display_atlas, &text, text_size);
+                let tx = right_x - tw;
+                let ty = text_center_y(&self.display_font, text_size, cy - text_size, text_size * 2.0);
+                rasterize_text(pixmap, &text, &self.display_font, &self.display_atlas, text_size,
+                    tx, ty, label_color.with_alpha(oa(230)));
+            }
+            MenuEntry::Slider(s) => {
+                let w = SLIDER_W * scale;
+                let h = SLIDER_H * scale;
+                let x = right_x - w;
+                let y = cy - h / 2.0;
+                draw_rounded_rect(pixmap, x, y, w, h, h / 2.0, self.subtle.with_alpha(oa(50)));
+                let fill_w = (w * s.value.clamp(0.0, 1.0)).max(h);
+                draw_gradient_rounded_rect(pixmap, x, y, fill_w, h, h / 2.0,
+                    self.accent.with_alpha(oa(230)), self.accent.blend(self.shadow, 0.2).with_alpha(oa(230)));
+            }
+            _ => {}
+        }
+    }
+
     fn measure_hints(&self, size: f32, scale: f32) -> f32 {
         let btn_d = size + 8.0 * scale;
         let gap = 4.0 * scale;
         let sep = 16.0 * scale;
-        let a_label = measure_text(&self.light_font, "Select", size);
-        let b_label = measure_text(&self.light_font, "Back", size);
+        let a_label = measure_text(&self.light_font, &self.light_atlas, "Select", size);
+        let b_label = measure_text(&self.light_font, &self.light_atlas, "Back", size);
         btn_d + gap + a_label + sep + btn_d + gap + b_label
     }
 
@@ -539,9 +901,9 @@ impl Renderer {
         draw_circle_stroke(pixmap, bcx, bcy, btn_r, border_color);
 
         // Letter centered in circle
-        let lw = measure_text(&self.body_font, button, size * 0.85);
+        let lw = measure_text(&self.body_font, &self.body_atlas, button, size * 0.85);
         let letter_y = text_center_y(&self.body_font, size * 0.85, bcy - btn_r, btn_d);
-        rasterize_text(pixmap, button, &self.body_font, size * 0.85,
+        rasterize_text(pixmap, button, &self.body_font, &self.body_atlas, size * 0.85,
             bcx - lw / 2.0, letter_y,
             self.fg.with_alpha(oa(200)));
 
@@ -549,16 +911,16 @@ impl Renderer {
         let gap = 4.0 * scale;
         let lx = x + btn_d + gap;
         let label_y = text_center_y(&self.light_font, size, bcy - btn_r, btn_d);
-        rasterize_text(pixmap, label, &self.light_font, size,
+        rasterize_text(pixmap, label, &self.light_font, &self.light_atlas, size,
             lx, label_y,
             self.fg.with_alpha(oa(80)));
 
-        lx + measure_text(&self.light_font, label, size)
+        lx + measure_text(&self.light_font, &self.light_atlas, label, size)
     }
 
     // ── Status pill ─────────────────────────────────────────
 
-    fn draw_status_pill(&self, pixmap: &mut Pixmap, game_name: Option<&str>, opacity: f32) {
+    fn draw_status_pill(&self, pixmap: &mut Pixmap, game_name: Option<&str>, opacity: f32, scale: f32) {
         let oa = |base: u8| -> u8 { (base as f32 * opacity) as u8 };
 
         let clock = {
@@ -571,38 +933,79 @@ impl Renderer {
             format!("{:02}:{:02}", hours, minutes)
         };
 
-        let text = match game_name {
-            Some(name) => format!("{}  \u{00B7}  {}", clock, name),
+        // The clock+separator prefix is app-generated ASCII and always LTR;
+        // only the game name itself (arbitrary, possibly RTL, content) goes
+        // through bidi reordering — keeping them separate avoids a leading
+        // digit run inheriting an RTL base direction from the name.
+        let prefix = match game_name {
+            Some(_) => format!("{}  \u{00B7}  ", clock),
             None => clock,
         };
 
-        let text_size = 12.0_f32;
-        let text_w = measure_text(&self.light_font, &text, text_size);
-        let pill_w = text_w + STATUS_PAD_H * 2.0;
-        let x = STATUS_MARGIN;
-        let y = STATUS_MARGIN;
+        let (status_h, status_radius, status_pad_h, status_margin) =
+            (STATUS_H * scale, STATUS_RADIUS * scale, STATUS_PAD_H * scale, STATUS_MARGIN * scale);
+        let status_style = self.styles.status_text;
+        let text_size = status_style.size * scale;
+        let font = self.font_for(status_style.family);
+        let atlas = self.atlas_for(status_style.family);
+        let prefix_w = measure_text(font, atlas, &prefix, text_size);
+        let name_w = game_name.map_or(0.0, |n| measure_text_bidi(font, atlas, n, text_size, bidi::Direction::Auto));
+        let pill_w = prefix_w + name_w + status_pad_h * 2.0;
+        let x = status_margin;
+        let y = status_margin;
 
         // Shadow
-        draw_rounded_rect(pixmap, x + 1.0, y + 2.0, pill_w, STATUS_H, STATUS_RADIUS,
+        draw_rounded_rect(pixmap, x + scale, y + 2.0 * scale, pill_w, status_h, status_radius,
             self.shadow.with_alpha(oa(40)));
 
         // Glass pill
-        draw_rounded_rect(pixmap, x, y, pill_w, STATUS_H, STATUS_RADIUS,
+        draw_rounded_rect(pixmap, x, y, pill_w, status_h, status_radius,
             self.card.with_alpha(oa(220)));
 
         // Border glow (accent tinted)
-        draw_rounded_rect_stroke(pixmap, x, y, pill_w, STATUS_H, STATUS_RADIUS,
+        draw_rounded_rect_stroke(pixmap, x, y, pill_w, status_h, status_radius,
             self.accent.with_alpha(oa(25)));
 
         // Top highlight
-        draw_rounded_rect(pixmap, x + 1.0, y + 1.0, pill_w - 2.0, 1.0, STATUS_RADIUS - 1.0,
+        draw_rounded_rect(pixmap, x + scale, y + scale, pill_w - 2.0 * scale, scale, status_radius - scale,
             self.subtle.with_alpha(oa(12)));
 
         // Text vertically centered in pill
-        let text_y = text_center_y(&self.light_font, text_size, y, STATUS_H);
-        rasterize_text(pixmap, &text, &self.light_font, text_size,
-            x + STATUS_PAD_H, text_y,
-            self.fg.with_alpha(oa(170)));
+        let text_y = text_center_y(self.font_for(status_style.family), text_size, y, status_h);
+        let color = self.color_for(status_style.color).with_alpha(oa(170));
+        self.draw_styled_text(pixmap, &prefix, &status_style, scale, x + status_pad_h, text_y, color);
+        if let Some(name) = game_name {
+            self.draw_styled_text_bidi(pixmap, name, &status_style, scale,
+                x + status_pad_h + prefix_w, text_y, color, bidi::Direction::Auto);
+        }
+    }
+
+    /// Draw one leaderboard tracker row (name left, value right) into a
+    /// standalone tracker-stack pixmap at `y`. Name is bidi-aware (RA content);
+    /// the value is app-generated and right-aligned in lieu of a true
+    /// monospace face, so it doesn't jitter the card width as digits change.
+    fn draw_tracker_row(&self, pixmap: &mut Pixmap, y: f32, name: &str, value: &str) {
+        let row_w = TRACKER_W;
+        let row_h = TRACKER_ROW_H;
+
+        draw_rounded_rect(pixmap, 0.0, y, row_w, row_h, TRACKER_RADIUS, self.card.with_alpha(230));
+        draw_rounded_rect_stroke(pixmap, 0.0, y, row_w, row_h, TRACKER_RADIUS, self.accent.with_alpha(50));
+
+        let label_style = self.styles.tracker_label;
+        let value_style = self.styles.tracker_value;
+        let value_size = value_style.size;
+        let value_w = measure_text(self.font_for(value_style.family), self.atlas_for(value_style.family), value, value_size);
+        let name_max_w = row_w - TRACKER_PAD_H * 2.0 - value_w - 8.0;
+
+        let name_trunc = truncate_to_width_bidi(self.font_for(label_style.family), self.atlas_for(label_style.family), name, label_style.size, name_max_w, bidi::Direction::Auto);
+        let name_y = text_center_y(self.font_for(label_style.family), label_style.size, y, row_h);
+        self.draw_styled_text_bidi(pixmap, &name_trunc, &label_style, 1.0,
+            TRACKER_PAD_H, name_y, self.color_for(label_style.color).with_alpha(230), bidi::Direction::Auto);
+
+        let value_y = text_center_y(self.font_for(value_style.family), value_size, y, row_h);
+        let value_x = row_w - TRACKER_PAD_H - value_w;
+        self.draw_styled_text(pixmap, value, &value_style, 1.0,
+            value_x, value_y, self.color_for(value_style.color).with_alpha(255));
     }
 }
 
@@ -744,42 +1147,226 @@ fn in_rounded_rect(x: f32, y: f32, w: f32, h: f32, r: f32) -> bool {
     true
 }
 
+/// A single codepoint's draw position, as produced by `layout_clusters`.
+/// Combining marks carry the same `x` as the base character they decorate,
+/// since the cluster they belong to only advances the cursor once.
+struct PositionedGlyph {
+    ch: char,
+    x: f32,
+}
+
+/// Pairwise kerning adjustment between `prev` (the previous cluster's base
+/// character, if any) and `ch`, as reported by fontdue's kerning table.
+/// Shared by `layout_clusters` and `truncate_to_width` so a rasterized
+/// string and its measured width always agree on where each glyph lands —
+/// otherwise truncation would mispredict where the ellipsis fits.
+fn kern_delta(font: &fontdue::Font, prev: Option<char>, ch: char, size: f32) -> f32 {
+    prev.and_then(|p| font.horizontal_kern(p, ch, size)).unwrap_or(0.0)
+}
+
+/// Lay `text` out left-to-right starting at `x`, grouping codepoints into
+/// extended grapheme clusters (combining-mark sequences, emoji ZWJ
+/// sequences, etc.) via `unicode-segmentation` rather than advancing per
+/// `char`. Every codepoint in a cluster is positioned at the cluster's
+/// start `x`; the cursor is first nudged by the pairwise kerning between
+/// the previous cluster's base character and this one (see `kern_delta`),
+/// then advances by the cluster's base (first) character's advance width,
+/// so marks stack on the base glyph instead of sliding past it. Returns the
+/// positioned glyphs plus the total advance past `x`, so callers needing
+/// only a width (`measure_text`) don't have to re-walk the clusters
+/// themselves.
+fn layout_clusters(atlas: &mut GlyphAtlas, font: &fontdue::Font, text: &str, size: f32, x: f32) -> (Vec<PositionedGlyph>, f32) {
+    let mut glyphs = Vec::new();
+    let mut cursor_x = x;
+    let mut prev_char = None;
+    for cluster in text.graphemes(true) {
+        let mut chars = cluster.chars();
+        let Some(base) = chars.next() else { continue };
+        cursor_x += kern_delta(font, prev_char, base, size);
+        glyphs.push(PositionedGlyph { ch: base, x: cursor_x });
+        for mark in chars {
+            glyphs.push(PositionedGlyph { ch: mark, x: cursor_x });
+        }
+        cursor_x += atlas.advance(font, base, size);
+        prev_char = Some(base);
+    }
+    (glyphs, cursor_x - x)
+}
+
+/// Alpha-blend a coverage bitmap (e.g. a rasterized glyph, or a dilated
+/// halo of one) onto `pixmap` at glyph-space origin (`gx`, `gy`), sized
+/// `w`x`h`, tinted by `c`. `coverage(row, col)` supplies the 0-255 coverage
+/// at each bitmap cell. Shared by the plain glyph fill pass and the
+/// outline/halo pass in `rasterize_text_outlined`, so both composite
+/// identically — only the coverage source and tint color differ.
+fn blit_coverage(pixmap: &mut Pixmap, gx: i32, gy: i32, w: usize, h: usize, c: Color8, coverage: impl Fn(usize, usize) -> u8) {
+    if c.a == 0 { return; }
+    let pw = pixmap.width() as i32;
+    let ph = pixmap.height() as i32;
+    let data = pixmap.data_mut();
+    for row in 0..h {
+        for col in 0..w {
+            let px = gx + col as i32;
+            let py = gy + row as i32;
+            if px < 0 || py < 0 || px >= pw || py >= ph { continue; }
+            let cov = coverage(row, col);
+            if cov == 0 { continue; }
+            let alpha = ((cov as u32 * c.a as u32) / 255) as u8;
+            let idx = ((py as u32 * pw as u32 + px as u32) * 4) as usize;
+            let inv = 255 - alpha;
+            data[idx] = ((c.r as u32 * alpha as u32 + data[idx] as u32 * inv as u32) / 255) as u8;
+            data[idx + 1] = ((c.g as u32 * alpha as u32 + data[idx + 1] as u32 * inv as u32) / 255) as u8;
+            data[idx + 2] = ((c.b as u32 * alpha as u32 + data[idx + 2] as u32 * inv as u32) / 255) as u8;
+            data[idx + 3] = ((alpha as u32 + data[idx + 3] as u32 * inv as u32 / 255).min(255)) as u8;
+        }
+    }
+}
+
 /// Rasterize text with y = top of text em-box (not baseline).
-/// Computes baseline internally from font ascent metrics.
-fn rasterize_text(pixmap: &mut Pixmap, text: &str, font: &fontdue::Font, size: f32, x: f32, y: f32, c: Color8) {
+/// Computes baseline internally from font ascent metrics. Glyph bitmaps are
+/// looked up from `atlas`, which rasterizes (and caches) on first use.
+/// Text is laid out by grapheme cluster (see `layout_clusters`) so combining
+/// marks are drawn on top of their base glyph instead of after it.
+fn rasterize_text(pixmap: &mut Pixmap, text: &str, font: &fontdue::Font, atlas: &RefCell<GlyphAtlas>, size: f32, x: f32, y: f32, c: Color8) {
+    if c.a == 0 { return; }
+    let ascent = font.horizontal_line_metrics(size)
+        .map(|lm| lm.ascent)
+        .unwrap_or(size * 0.8);
+    let baseline_y = y + ascent;
+
+    let mut atlas = atlas.borrow_mut();
+    let (glyphs, _) = layout_clusters(&mut atlas, font, text, size, x);
+    for pg in glyphs {
+        let glyph = atlas.glyph(font, pg.ch, size);
+        let gx = pg.x as i32 + glyph.xmin;
+        let gy = baseline_y as i32 - glyph.ymin - glyph.height as i32;
+        blit_coverage(pixmap, gx, gy, glyph.width as usize, glyph.height as usize, c,
+            |row, col| atlas.coverage(&glyph, row, col));
+    }
+}
+
+/// Clip-bound version of `blit_coverage`: identical compositing, but pixels
+/// with `px` outside `[clip_x0, clip_x1)` are skipped in addition to the
+/// usual pixmap bounds check. Used by `rasterize_text_clipped` so marquee-
+/// scrolled text can be confined to its box without touching the hot-path
+/// `blit_coverage` every other caller uses.
+fn blit_coverage_clipped(pixmap: &mut Pixmap, gx: i32, gy: i32, w: usize, h: usize, c: Color8, clip_x0: f32, clip_x1: f32, coverage: impl Fn(usize, usize) -> u8) {
     if c.a == 0 { return; }
     let pw = pixmap.width() as i32;
     let ph = pixmap.height() as i32;
     let data = pixmap.data_mut();
+    for row in 0..h {
+        for col in 0..w {
+            let px = gx + col as i32;
+            let py = gy + row as i32;
+            if px < 0 || py < 0 || px >= pw || py >= ph { continue; }
+            if (px as f32) < clip_x0 || (px as f32) >= clip_x1 { continue; }
+            let cov = coverage(row, col);
+            if cov == 0 { continue; }
+            let alpha = ((cov as u32 * c.a as u32) / 255) as u8;
+            let idx = ((py as u32 * pw as u32 + px as u32) * 4) as usize;
+            let inv = 255 - alpha;
+            data[idx] = ((c.r as u32 * alpha as u32 + data[idx] as u32 * inv as u32) / 255) as u8;
+            data[idx + 1] = ((c.g as u32 * alpha as u32 + data[idx + 1] as u32 * inv as u32) / 255) as u8;
+            data[idx + 2] = ((c.b as u32 * alpha as u32 + data[idx + 2] as u32 * inv as u32) / 255) as u8;
+            data[idx + 3] = ((alpha as u32 + data[idx + 3] as u32 * inv as u32 / 255).min(255)) as u8;
+        }
+    }
+}
 
+/// Clip-bound version of `rasterize_text`, for the marquee-scroll pass in
+/// `draw_fitted_bidi`: draws the whole (unclipped-width) string but only
+/// the portion whose horizontal extent falls within `[clip_x0, clip_x1)`
+/// actually composites, so a scrolled line stays confined to its box.
+fn rasterize_text_clipped(pixmap: &mut Pixmap, text: &str, font: &fontdue::Font, atlas: &RefCell<GlyphAtlas>, size: f32, x: f32, y: f32, c: Color8, clip_x0: f32, clip_x1: f32) {
+    if c.a == 0 { return; }
     let ascent = font.horizontal_line_metrics(size)
         .map(|lm| lm.ascent)
         .unwrap_or(size * 0.8);
     let baseline_y = y + ascent;
 
-    let mut cursor_x = x;
-    for ch in text.chars() {
-        let (metrics, bitmap) = font.rasterize(ch, size);
-        let gx = cursor_x as i32 + metrics.xmin;
-        let gy = baseline_y as i32 - metrics.ymin - metrics.height as i32;
-
-        for row in 0..metrics.height {
-            for col in 0..metrics.width {
-                let px = gx + col as i32;
-                let py = gy + row as i32;
-                if px < 0 || py < 0 || px >= pw || py >= ph { continue; }
-                let coverage = bitmap[row * metrics.width + col];
-                if coverage == 0 { continue; }
-                let alpha = ((coverage as u32 * c.a as u32) / 255) as u8;
-                let idx = ((py as u32 * pw as u32 + px as u32) * 4) as usize;
-                let inv = 255 - alpha;
-                data[idx] = ((c.r as u32 * alpha as u32 + data[idx] as u32 * inv as u32) / 255) as u8;
-                data[idx + 1] = ((c.g as u32 * alpha as u32 + data[idx + 1] as u32 * inv as u32) / 255) as u8;
-                data[idx + 2] = ((c.b as u32 * alpha as u32 + data[idx + 2] as u32 * inv as u32) / 255) as u8;
-                data[idx + 3] = ((alpha as u32 + data[idx + 3] as u32 * inv as u32 / 255).min(255)) as u8;
+    let mut atlas = atlas.borrow_mut();
+    let (glyphs, _) = layout_clusters(&mut atlas, font, text, size, x);
+    for pg in glyphs {
+        let glyph = atlas.glyph(font, pg.ch, size);
+        let gx = pg.x as i32 + glyph.xmin;
+        let gy = baseline_y as i32 - glyph.ymin - glyph.height as i32;
+        blit_coverage_clipped(pixmap, gx, gy, glyph.width as usize, glyph.height as usize, c, clip_x0, clip_x1,
+            |row, col| atlas.coverage(&glyph, row, col));
+    }
+}
+
+/// Morphological dilation of a glyph's coverage bitmap by `radius` pixels,
+/// approximated as a disk: each output pixel takes the max coverage found
+/// within `radius` of it in the source bitmap. This is the outline/halo
+/// approach from the FreeType-stroker technique used in Godot's font
+/// renderer, adapted to fontdue's coverage-only glyphs (no outline
+/// contours to stroke directly). The returned bitmap is `2 * radius`
+/// pixels wider and taller than `glyph`, padded evenly on every side —
+/// callers should offset their draw origin by `-radius` in both axes.
+fn dilate_coverage(atlas: &GlyphAtlas, glyph: &GlyphInfo, radius: usize) -> Vec<u8> {
+    let (gw, gh) = (glyph.width as i32, glyph.height as i32);
+    let (w, h) = (gw as usize + 2 * radius, gh as usize + 2 * radius);
+    let r = radius as i32;
+    let mut out = vec![0u8; w * h];
+    for oy in 0..h as i32 {
+        for ox in 0..w as i32 {
+            let (cx, cy) = (ox - r, oy - r);
+            let mut max_cov = 0u8;
+            for dy in -r..=r {
+                for dx in -r..=r {
+                    if dx * dx + dy * dy > r * r { continue; }
+                    let (sx, sy) = (cx + dx, cy + dy);
+                    if sx < 0 || sy < 0 || sx >= gw || sy >= gh { continue; }
+                    let cov = atlas.coverage(glyph, sy as usize, sx as usize);
+                    if cov > max_cov { max_cov = cov; }
+                }
             }
+            out[(oy as usize) * w + ox as usize] = max_cov;
         }
-        cursor_x += metrics.advance_width;
+    }
+    out
+}
+
+/// Outlined/haloed version of `rasterize_text`: if `outline_width <= 0.0`
+/// this is identical to `rasterize_text` (no behavior change when the
+/// feature is unused). Otherwise each glyph is composited twice — first a
+/// halo pass (the glyph's coverage dilated by `outline_width` pixels, see
+/// `dilate_coverage`, tinted `outline_color`) so a ring of the outline
+/// color surrounds the glyph, then the normal fill pass on top in `c` —
+/// keeping thin glyphs legible over busy gradient backgrounds like
+/// `draw_gradient_rounded_rect` panels.
+fn rasterize_text_outlined(pixmap: &mut Pixmap, text: &str, font: &fontdue::Font, atlas: &RefCell<GlyphAtlas>, size: f32, x: f32, y: f32, c: Color8, outline_width: f32, outline_color: Color8) {
+    if outline_width <= 0.0 {
+        rasterize_text(pixmap, text, font, atlas, size, x, y, c);
+        return;
+    }
+    if c.a == 0 && outline_color.a == 0 { return; }
+    let ascent = font.horizontal_line_metrics(size)
+        .map(|lm| lm.ascent)
+        .unwrap_or(size * 0.8);
+    let baseline_y = y + ascent;
+    let radius = (outline_width.round() as i32).max(1) as usize;
+
+    let mut atlas = atlas.borrow_mut();
+    let (glyphs, _) = layout_clusters(&mut atlas, font, text, size, x);
+
+    // Halo pass first, so the fill pass composites on top of it.
+    for pg in &glyphs {
+        let glyph = atlas.glyph(font, pg.ch, size);
+        let dilated = dilate_coverage(&atlas, &glyph, radius);
+        let w = glyph.width as usize + 2 * radius;
+        let h = glyph.height as usize + 2 * radius;
+        let gx = pg.x as i32 + glyph.xmin - radius as i32;
+        let gy = baseline_y as i32 - glyph.ymin - glyph.height as i32 - radius as i32;
+        blit_coverage(pixmap, gx, gy, w, h, outline_color, |row, col| dilated[row * w + col]);
+    }
+    for pg in &glyphs {
+        let glyph = atlas.glyph(font, pg.ch, size);
+        let gx = pg.x as i32 + glyph.xmin;
+        let gy = baseline_y as i32 - glyph.ymin - glyph.height as i32;
+        blit_coverage(pixmap, gx, gy, glyph.width as usize, glyph.height as usize, c,
+            |row, col| atlas.coverage(&glyph, row, col));
     }
 }
 
@@ -790,31 +1377,211 @@ fn text_height(font: &fontdue::Font, size: f32) -> f32 {
         .unwrap_or(size)
 }
 
+/// Horizontal alignment for a wrapped block of text (naming matches
+/// fonterator's `TextAlign`). Only affects each line's starting `x` within
+/// the box — a single unbroken line is still measured the same way
+/// regardless of alignment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// One wrapped line, ready to draw: its text and the `x` offset from the
+/// box's left edge, per `Align`.
+struct WrappedLine {
+    text: String,
+    x_offset: f32,
+}
+
+/// Greedily wrap `text` into lines no wider than `max_width`: break at
+/// whitespace grapheme boundaries first, falling back to a mid-word break
+/// (still by whole grapheme cluster) for a single word wider than
+/// `max_width` on its own. Each line's width is measured with
+/// `measure_text` — the same shared advance helper `rasterize_text` and
+/// `truncate_to_width` use — so wrapping agrees with how the lines
+/// actually render, and that width is used to offset the line's `x` per
+/// `align`.
+fn wrap_text(font: &fontdue::Font, atlas: &RefCell<GlyphAtlas>, text: &str, size: f32, max_width: f32, align: Align) -> Vec<WrappedLine> {
+    let space_w = measure_text(font, atlas, " ", size);
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut current_w = 0.0;
+
+    for word in text.split_whitespace() {
+        let word_w = measure_text(font, atlas, word, size);
+
+        if word_w > max_width {
+            // Unbreakable word — flush what we have, then hard-wrap the
+            // word itself by grapheme cluster.
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                current_w = 0.0;
+            }
+            for cluster in word.graphemes(true) {
+                let cw = measure_text(font, atlas, cluster, size);
+                if !current.is_empty() && current_w + cw > max_width {
+                    lines.push(std::mem::take(&mut current));
+                    current_w = 0.0;
+                }
+                current.push_str(cluster);
+                current_w += cw;
+            }
+            continue;
+        }
+
+        if !current.is_empty() && current_w + space_w + word_w > max_width {
+            lines.push(std::mem::take(&mut current));
+            current_w = 0.0;
+        }
+        if !current.is_empty() {
+            current.push(' ');
+            current_w += space_w;
+        }
+        current.push_str(word);
+        current_w += word_w;
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+        .into_iter()
+        .map(|line| {
+            let w = measure_text(font, atlas, &line, size);
+            let x_offset = match align {
+                Align::Left => 0.0,
+                Align::Center => (max_width - w) / 2.0,
+                Align::Right => max_width - w,
+            };
+            WrappedLine { text: line, x_offset }
+        })
+        .collect()
+}
+
+/// Row height (before scaling) a menu entry occupies in the panel.
+fn entry_height(entry: &MenuEntry) -> f32 {
+    match entry {
+        MenuEntry::Header(_) => MENU_HEADER_H,
+        MenuEntry::Spacer(s) => s.spacer.max(0.0),
+        _ => MENU_ITEM_H,
+    }
+}
+
 /// Compute y (top of em-box) to vertically center text within a container.
 fn text_center_y(font: &fontdue::Font, size: f32, container_y: f32, container_h: f32) -> f32 {
     container_y + (container_h - text_height(font, size)) / 2.0
 }
 
-pub fn measure_text(font: &fontdue::Font, text: &str, size: f32) -> f32 {
-    text.chars().map(|ch| font.metrics(ch, size).advance_width).sum()
+/// How `draw_fitted_bidi` should handle text that doesn't fit its box.
+pub enum TextFit {
+    /// Draw as-is, or ellipsis-truncated — the box is wide enough, or close
+    /// enough that truncating reads better than scrolling.
+    Static,
+    /// Draw the full string, clipped to the box, shifted left by `scroll_x`
+    /// pixels of its current marquee position.
+    Marquee { scroll_x: f32 },
+}
+
+/// Decide how a string of measured width `text_w` should be fit into
+/// `max_width`, given `cfg`'s marquee policy and `elapsed_ms` (the caller's
+/// free-running clock — `Popup::elapsed_ms` or `Menu::marquee_elapsed_ms`).
+/// Overflow at or below `cfg.marquee_threshold` (or marqueeing disabled
+/// entirely) truncates instead of scrolling, since a few pixels of overflow
+/// reads better as an ellipsis than a constantly-moving line. Otherwise the
+/// text scrolls its full overflow distance, dwells `cfg.marquee_pause_ms` at
+/// each end, and loops.
+pub fn fit_text(text_w: f32, max_width: f32, cfg: &TextFitConfig, elapsed_ms: u64) -> TextFit {
+    let overflow = text_w - max_width;
+    if overflow <= cfg.marquee_threshold || !cfg.marquee_enabled {
+        return TextFit::Static;
+    }
+
+    let scroll_ms = ((overflow / cfg.marquee_speed) * 1000.0).max(1.0) as u64;
+    let period_ms = scroll_ms + cfg.marquee_pause_ms * 2;
+    let t = elapsed_ms % period_ms.max(1);
+
+    let scroll_x = if t < cfg.marquee_pause_ms {
+        0.0
+    } else if t < cfg.marquee_pause_ms + scroll_ms {
+        (t - cfg.marquee_pause_ms) as f32 / scroll_ms as f32 * overflow
+    } else {
+        overflow
+    };
+    TextFit::Marquee { scroll_x }
+}
+
+pub fn measure_text(font: &fontdue::Font, atlas: &RefCell<GlyphAtlas>, text: &str, size: f32) -> f32 {
+    let mut atlas = atlas.borrow_mut();
+    layout_clusters(&mut atlas, font, text, size, 0.0).1
 }
 
-pub fn truncate_to_width(font: &fontdue::Font, text: &str, size: f32, max_width: f32) -> String {
+/// Truncates by whole grapheme cluster, never slicing a combining-mark
+/// sequence or ZWJ emoji in half to make room for the ellipsis. Tracks the
+/// same running `prev_char`/kerning used by `layout_clusters` (see
+/// `kern_delta`) so the cutoff point agrees with how the string actually
+/// rasterizes.
+pub fn truncate_to_width(font: &fontdue::Font, atlas: &RefCell<GlyphAtlas>, text: &str, size: f32, max_width: f32) -> String {
     let mut result = String::new();
     let mut width = 0.0;
-    let ellipsis_width = measure_text(font, "...", size);
-    for ch in text.chars() {
-        let cw = font.metrics(ch, size).advance_width;
+    let ellipsis_width = measure_text(font, atlas, "...", size);
+    let mut atlas_ref = atlas.borrow_mut();
+    let mut prev_char = None;
+    for cluster in text.graphemes(true) {
+        let Some(base) = cluster.chars().next() else { continue };
+        let cw = kern_delta(font, prev_char, base, size) + atlas_ref.advance(font, base, size);
         if width + cw + ellipsis_width > max_width && !result.is_empty() {
             result.push_str("...");
             return result;
         }
         width += cw;
-        result.push(ch);
+        result.push_str(cluster);
+        prev_char = Some(base);
     }
     result
 }
 
+/// Bidi-aware width measurement: reorders `text` into visual runs (see
+/// `bidi::visual_runs`) and sums each run's width. Identical to
+/// `measure_text` for a plain LTR string.
+pub fn measure_text_bidi(font: &fontdue::Font, atlas: &RefCell<GlyphAtlas>, text: &str, size: f32, direction: bidi::Direction) -> f32 {
+    bidi::visual_runs(text, direction)
+        .iter()
+        .map(|(run, _)| measure_text(font, atlas, run, size))
+        .sum()
+}
+
+/// Bidi-aware version of `rasterize_text`: reorders `text` into visual runs
+/// and draws them left to right starting at `x`, so the caller's `x` is
+/// always the visual left edge regardless of script direction.
+fn rasterize_text_bidi(pixmap: &mut Pixmap, text: &str, font: &fontdue::Font, atlas: &RefCell<GlyphAtlas>, size: f32, x: f32, y: f32, c: Color8, direction: bidi::Direction) {
+    let mut cursor_x = x;
+    for (run, _rtl) in bidi::visual_runs(text, direction) {
+        rasterize_text(pixmap, &run, font, atlas, size, cursor_x, y, c);
+        cursor_x += measure_text(font, atlas, &run, size);
+    }
+}
+
+/// Bidi-aware version of `truncate_to_width`. Grapheme clusters are dropped
+/// from the *logical* end of `text` (not the visual end, which would depend
+/// on script direction, and never splitting a cluster in half), then the
+/// truncated-plus-ellipsis string is measured in its reordered visual form
+/// so the ellipsis lands in the right spot.
+pub fn truncate_to_width_bidi(font: &fontdue::Font, atlas: &RefCell<GlyphAtlas>, text: &str, size: f32, max_width: f32, direction: bidi::Direction) -> String {
+    if measure_text_bidi(font, atlas, text, size, direction) <= max_width {
+        return text.to_string();
+    }
+    let clusters: Vec<&str> = text.graphemes(true).collect();
+    for len in (0..clusters.len()).rev() {
+        let candidate: String = clusters[..len].iter().copied().chain(std::iter::once("...")).collect();
+        if len == 0 || measure_text_bidi(font, atlas, &candidate, size, direction) <= max_width {
+            return candidate;
+        }
+    }
+    "...".to_string()
+}
+
 pub fn pixmap_to_argb(pixmap: &Pixmap) -> Vec<u32> {
     let data = pixmap.data();
     let mut argb = Vec::with_capacity(data.len() / 4);
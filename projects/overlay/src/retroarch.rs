@@ -1,14 +1,33 @@
 //! RetroArch network command client (UDP).
 
+use std::io::ErrorKind;
 use std::net::UdpSocket;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use log::{debug, warn};
 
+/// How often `spawn_status_poll` queries `GET_STATUS`.
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How long a single status poll waits for a reply before giving up on it
+/// (not the whole polling loop, just that one round).
+const STATUS_QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+
 pub struct RetroArchClient {
     socket: UdpSocket,
     addr: String,
 }
 
+/// Parsed reply to a `GET_STATUS` query: whether content is loaded and
+/// running, plus `(core, game)` when it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunState {
+    Contentless,
+    Paused(String, String),
+    Playing(String, String),
+}
+
 impl RetroArchClient {
     pub fn new(host: &str, port: u16) -> Result<Self, String> {
         let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| format!("udp bind: {e}"))?;
@@ -29,4 +48,152 @@ impl RetroArchClient {
             }
         }
     }
+
+    /// Send `command` and poll the (already-nonblocking) socket for a reply
+    /// until `timeout` elapses. RetroArch may answer from a different port
+    /// than the one we sent to, so any source address is accepted. Returns
+    /// `None` on a send failure, a malformed (non-UTF-8) reply, or timeout.
+    pub fn query(&self, command: &str, timeout: Duration) -> Option<String> {
+        if !self.send_command(command) {
+            return None;
+        }
+        let deadline = Instant::now() + timeout;
+        let mut buf = [0u8; 4096];
+        loop {
+            match self.socket.recv_from(&mut buf) {
+                Ok((n, _src)) => return std::str::from_utf8(&buf[..n]).ok().map(|s| s.trim_end().to_string()),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    if Instant::now() >= deadline {
+                        return None;
+                    }
+                    thread::sleep(Duration::from_millis(5));
+                }
+                Err(e) => {
+                    warn!("Failed to read RA reply to '{command}': {e}");
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Query `GET_STATUS` and parse the reply into a `RunState`. Returns
+    /// `None` on timeout or a reply that doesn't match the expected shape
+    /// (see `parse_status_reply`).
+    pub fn get_status(&self, timeout: Duration) -> Option<RunState> {
+        let reply = self.query("GET_STATUS", timeout)?;
+        parse_status_reply(&reply)
+    }
+
+    /// Query `VERSION`, returning the bare version string.
+    pub fn get_version(&self, timeout: Duration) -> Option<String> {
+        self.query("VERSION", timeout)
+    }
+
+    /// Query `READ_CORE_RAM <addr> <len>`, returning the space-separated
+    /// hex byte payload (the reply echoes `READ_CORE_RAM <addr>` back
+    /// before the bytes, which is stripped off here).
+    pub fn read_core_ram(&self, addr: &str, len: u32, timeout: Duration) -> Option<String> {
+        let reply = self.query(&format!("READ_CORE_RAM {addr} {len}"), timeout)?;
+        reply.strip_prefix(&format!("READ_CORE_RAM {addr} ")).map(str::to_string)
+    }
+}
+
+/// Parse a `GET_STATUS` reply body: `GET_STATUS CONTENTLESS`,
+/// `GET_STATUS PAUSED <core>,<game>,crc32=...`, or
+/// `GET_STATUS PLAYING <core>,<game>,crc32=...`. `None` on anything that
+/// doesn't match this shape — a different/malformed reply, or a
+/// PAUSED/PLAYING payload missing its core or game field.
+fn parse_status_reply(reply: &str) -> Option<RunState> {
+    let (tag, rest) = reply.split_once(' ').unwrap_or((reply, ""));
+    if tag != "GET_STATUS" {
+        return None;
+    }
+    let (state, payload) = rest.split_once(' ').map_or((rest, None), |(s, p)| (s, Some(p)));
+    match state {
+        "CONTENTLESS" => Some(RunState::Contentless),
+        "PAUSED" | "PLAYING" => {
+            let mut fields = payload?.splitn(3, ',');
+            let core = fields.next()?.to_string();
+            let game = fields.next()?.to_string();
+            if state == "PAUSED" {
+                Some(RunState::Paused(core, game))
+            } else {
+                Some(RunState::Playing(core, game))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Spawn a thread that polls `GET_STATUS` on `STATUS_POLL_INTERVAL` and
+/// reports every successfully parsed `RunState` over `tx`. A poll that
+/// times out (RetroArch not running, or between content loads) is silently
+/// skipped rather than reported — the caller keeps showing the last known
+/// state. Exits quietly if the socket itself can't be opened.
+pub fn spawn_status_poll(host: String, port: u16, tx: Sender<RunState>) {
+    thread::spawn(move || {
+        let client = match RetroArchClient::new(&host, port) {
+            Ok(c) => c,
+            Err(e) => {
+                warn!("RetroArch status polling disabled: {e}");
+                return;
+            }
+        };
+        loop {
+            if let Some(state) = client.get_status(STATUS_QUERY_TIMEOUT) {
+                if tx.send(state).is_err() {
+                    break;
+                }
+            }
+            thread::sleep(STATUS_POLL_INTERVAL);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_contentless() {
+        assert_eq!(parse_status_reply("GET_STATUS CONTENTLESS"), Some(RunState::Contentless));
+    }
+
+    #[test]
+    fn parses_playing_with_crc() {
+        let reply = "GET_STATUS PLAYING snes9x,Super Game,crc32=deadbeef";
+        assert_eq!(
+            parse_status_reply(reply),
+            Some(RunState::Playing("snes9x".to_string(), "Super Game".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_paused() {
+        let reply = "GET_STATUS PAUSED genesis_plus_gx,Sonic,crc32=cafef00d";
+        assert_eq!(
+            parse_status_reply(reply),
+            Some(RunState::Paused("genesis_plus_gx".to_string(), "Sonic".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_tag() {
+        assert_eq!(parse_status_reply("VERSION 1.9.0"), None);
+    }
+
+    #[test]
+    fn rejects_unknown_state() {
+        assert_eq!(parse_status_reply("GET_STATUS UNKNOWN"), None);
+    }
+
+    #[test]
+    fn rejects_missing_payload() {
+        assert_eq!(parse_status_reply("GET_STATUS PLAYING"), None);
+    }
+
+    #[test]
+    fn rejects_payload_missing_game_field() {
+        assert_eq!(parse_status_reply("GET_STATUS PLAYING snes9x"), None);
+    }
 }
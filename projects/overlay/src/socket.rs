@@ -1,11 +1,19 @@
 //! Unix domain socket listener for external menu commands.
+//!
+//! Supports two framing modes per connection, detected from the first byte:
+//! a legacy plaintext line format (`MENU_TOGGLE`, `POPUP title|description`),
+//! and a structured newline-delimited JSON format (first byte `{`) that can
+//! carry a badge image path, a custom display duration, and priority.
 
 use std::io::{BufRead, BufReader};
 use std::os::unix::net::UnixListener;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
 
 use log::{debug, info, warn};
+use serde::Deserialize;
+
+use crate::config::MenuEntry;
 
 #[derive(Debug)]
 pub enum SocketCommand {
@@ -14,7 +22,71 @@ pub enum SocketCommand {
     MenuDown,
     MenuSelect,
     MenuBack,
-    Popup { title: String, description: String },
+    /// Type one character into the menu's search/filter box.
+    MenuSearchChar(char),
+    /// Delete the character before the search box's caret.
+    MenuSearchBackspace,
+    MenuSearchCaretLeft,
+    MenuSearchCaretRight,
+    Popup {
+        title: String,
+        description: String,
+        /// Path to a badge image, loaded and decoded by the overlay itself —
+        /// callers never send raw image bytes over the socket.
+        badge_path: Option<PathBuf>,
+        duration_ms: Option<u32>,
+        priority: i32,
+    },
+    /// Replace the quick menu's item list wholesale.
+    MenuSetItems(Vec<MenuEntry>),
+    /// Update the game name shown in the status pill.
+    SetGameName(String),
+}
+
+/// Wire format for the JSON framing mode. Tagged on `cmd`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum JsonMessage {
+    MenuToggle,
+    MenuUp,
+    MenuDown,
+    MenuSelect,
+    MenuBack,
+    MenuSearchChar { ch: char },
+    MenuSearchBackspace,
+    MenuSearchCaretLeft,
+    MenuSearchCaretRight,
+    Popup {
+        title: String,
+        description: String,
+        badge_path: Option<PathBuf>,
+        duration_ms: Option<u32>,
+        #[serde(default)]
+        priority: i32,
+    },
+    MenuSetItems { items: Vec<MenuEntry> },
+    SetGameName { name: String },
+}
+
+impl From<JsonMessage> for SocketCommand {
+    fn from(msg: JsonMessage) -> Self {
+        match msg {
+            JsonMessage::MenuToggle => SocketCommand::MenuToggle,
+            JsonMessage::MenuUp => SocketCommand::MenuUp,
+            JsonMessage::MenuDown => SocketCommand::MenuDown,
+            JsonMessage::MenuSelect => SocketCommand::MenuSelect,
+            JsonMessage::MenuBack => SocketCommand::MenuBack,
+            JsonMessage::MenuSearchChar { ch } => SocketCommand::MenuSearchChar(ch),
+            JsonMessage::MenuSearchBackspace => SocketCommand::MenuSearchBackspace,
+            JsonMessage::MenuSearchCaretLeft => SocketCommand::MenuSearchCaretLeft,
+            JsonMessage::MenuSearchCaretRight => SocketCommand::MenuSearchCaretRight,
+            JsonMessage::Popup { title, description, badge_path, duration_ms, priority } => {
+                SocketCommand::Popup { title, description, badge_path, duration_ms, priority }
+            }
+            JsonMessage::MenuSetItems { items } => SocketCommand::MenuSetItems(items),
+            JsonMessage::SetGameName { name } => SocketCommand::SetGameName(name),
+        }
+    }
 }
 
 /// Listen for commands on a Unix domain socket. Blocks forever.
@@ -30,11 +102,20 @@ pub fn listen(path: &str, tx: Sender<SocketCommand>) -> Result<(), String> {
     for stream in listener.incoming() {
         match stream {
             Ok(stream) => {
-                let reader = BufReader::new(stream);
+                let mut reader = BufReader::new(stream);
+                // Peek the first byte (without consuming it) to pick a framing mode
+                // for the whole connection.
+                let json_mode = matches!(reader.fill_buf(), Ok(buf) if buf.first() == Some(&b'{'));
+
                 for line in reader.lines() {
                     match line {
                         Ok(line) => {
-                            if let Some(cmd) = parse_command(&line) {
+                            let cmd = if json_mode {
+                                parse_json_command(&line)
+                            } else {
+                                parse_command(&line)
+                            };
+                            if let Some(cmd) = cmd {
                                 debug!("Socket command: {line}");
                                 if tx.send(cmd).is_err() {
                                     return Err("channel closed".into());
@@ -62,18 +143,41 @@ fn parse_command(line: &str) -> Option<SocketCommand> {
         "MENU_DOWN" => Some(SocketCommand::MenuDown),
         "MENU_SELECT" => Some(SocketCommand::MenuSelect),
         "MENU_BACK" => Some(SocketCommand::MenuBack),
+        "MENU_SEARCH_BACKSPACE" => Some(SocketCommand::MenuSearchBackspace),
+        "MENU_SEARCH_LEFT" => Some(SocketCommand::MenuSearchCaretLeft),
+        "MENU_SEARCH_RIGHT" => Some(SocketCommand::MenuSearchCaretRight),
+        s if s.starts_with("MENU_SEARCH_CHAR ") => {
+            s[17..].chars().next().map(SocketCommand::MenuSearchChar)
+        }
         s if s.starts_with("POPUP ") => {
             let rest = &s[6..];
             let mut parts = rest.splitn(2, '|');
             Some(SocketCommand::Popup {
                 title: parts.next().unwrap_or("").to_string(),
                 description: parts.next().unwrap_or("").to_string(),
+                badge_path: None,
+                duration_ms: None,
+                priority: 0,
             })
         }
         _ => None,
     }
 }
 
+fn parse_json_command(line: &str) -> Option<SocketCommand> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    match serde_json::from_str::<JsonMessage>(line) {
+        Ok(msg) => Some(msg.into()),
+        Err(e) => {
+            warn!("Malformed JSON socket message: {e}");
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,7 +193,7 @@ mod tests {
 
     #[test]
     fn parse_popup_command() {
-        if let Some(SocketCommand::Popup { title, description }) = parse_command("POPUP First Blood|Defeat the boss") {
+        if let Some(SocketCommand::Popup { title, description, .. }) = parse_command("POPUP First Blood|Defeat the boss") {
             assert_eq!(title, "First Blood");
             assert_eq!(description, "Defeat the boss");
         } else {
@@ -102,4 +206,30 @@ mod tests {
         assert!(parse_command("GARBAGE").is_none());
         assert!(parse_command("").is_none());
     }
+
+    #[test]
+    fn parse_json_popup_command() {
+        let json = r#"{"cmd":"popup","title":"First Blood","description":"Defeat the boss","badge_path":"/tmp/badge.png","duration_ms":4000,"priority":2}"#;
+        match parse_json_command(json) {
+            Some(SocketCommand::Popup { title, description, badge_path, duration_ms, priority }) => {
+                assert_eq!(title, "First Blood");
+                assert_eq!(description, "Defeat the boss");
+                assert_eq!(badge_path, Some(PathBuf::from("/tmp/badge.png")));
+                assert_eq!(duration_ms, Some(4000));
+                assert_eq!(priority, 2);
+            }
+            _ => panic!("expected Popup"),
+        }
+    }
+
+    #[test]
+    fn parse_json_menu_toggle() {
+        let json = r#"{"cmd":"menu_toggle"}"#;
+        assert!(matches!(parse_json_command(json), Some(SocketCommand::MenuToggle)));
+    }
+
+    #[test]
+    fn parse_json_malformed_returns_none() {
+        assert!(parse_json_command("{not valid json").is_none());
+    }
 }
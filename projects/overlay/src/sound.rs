@@ -0,0 +1,273 @@
+//! Mixer-based playback for UI sound cues (toast chime, menu move/select/back).
+//!
+//! Cues are decoded once into owned mono PCM buffers at load time — WAV via
+//! a small hand-rolled parser, Ogg Vorbis via `lewton` — then resampled to
+//! the output device's sample rate. Triggering playback just pushes a fresh
+//! `Voice` (a cloned cue plus a read cursor) onto a shared mixer running on
+//! its own `cpal` stream thread, so overlapping triggers mix together
+//! instead of cutting each other off, and rendering never blocks on audio.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use log::{error, info, warn};
+
+use crate::config::SoundConfig;
+
+/// A cue decoded once into mono PCM at the mixer's output sample rate.
+#[derive(Clone)]
+struct Cue {
+    samples: Arc<[i16]>,
+}
+
+/// An in-flight playback position into a loaded `Cue`.
+struct Voice {
+    cue: Cue,
+    pos: usize,
+}
+
+struct MixerState {
+    voices: Vec<Voice>,
+    volume: f32,
+}
+
+/// Minimum gap between two `play_menu_move` triggers — holding a direction
+/// key fires far more often than the ear can distinguish discrete clicks, so
+/// a trigger within this window of the last one that played is dropped.
+const MOVE_DEBOUNCE: Duration = Duration::from_millis(40);
+
+/// Owns the output stream and the decoded per-event cues.
+pub struct SoundPlayer {
+    state: Arc<Mutex<MixerState>>,
+    _stream: cpal::Stream,
+    toast: Option<Cue>,
+    menu_move: Option<Cue>,
+    menu_select: Option<Cue>,
+    menu_back: Option<Cue>,
+    last_move: Mutex<Option<Instant>>,
+}
+
+impl SoundPlayer {
+    /// Open the default output device and decode the configured cues.
+    /// Returns `None` if sound is disabled or no output device is available —
+    /// callers should treat playback as a best-effort feature.
+    pub fn new(config: &SoundConfig, sounds_dir: &Path) -> Option<Self> {
+        if !config.enabled {
+            info!("Sound cues disabled by config");
+            return None;
+        }
+
+        let host = cpal::default_host();
+        let device = host.default_output_device().or_else(|| {
+            warn!("No default audio output device; sound cues disabled");
+            None
+        })?;
+        let supported = device.default_output_config().ok()?;
+        let sample_rate = supported.sample_rate().0;
+        let channels = supported.channels();
+
+        let state = Arc::new(Mutex::new(MixerState {
+            voices: Vec::new(),
+            volume: config.volume.clamp(0.0, 1.0),
+        }));
+
+        let mix_state = state.clone();
+        let stream = device
+            .build_output_stream(
+                &supported.config(),
+                move |data: &mut [f32], _| mix_into(&mix_state, data, channels),
+                |err| error!("Audio stream error: {err}"),
+                None,
+            )
+            .ok()?;
+        stream.play().ok()?;
+        info!("Sound mixer ready: {sample_rate}Hz x{channels}ch");
+
+        let load = |name: &Option<String>| -> Option<Cue> {
+            let name = name.as_ref()?;
+            let path = sounds_dir.join(name);
+            match decode_cue(&path, sample_rate) {
+                Ok(cue) => Some(cue),
+                Err(e) => {
+                    warn!("Failed to decode sound cue {}: {e}", path.display());
+                    None
+                }
+            }
+        };
+
+        Some(SoundPlayer {
+            state,
+            _stream: stream,
+            toast: load(&config.toast),
+            menu_move: load(&config.menu_move),
+            menu_select: load(&config.menu_select),
+            menu_back: load(&config.menu_back),
+            last_move: Mutex::new(None),
+        })
+    }
+
+    pub fn play_toast(&self) {
+        self.trigger(self.toast.as_ref());
+    }
+
+    /// Debounced — see [`MOVE_DEBOUNCE`].
+    pub fn play_menu_move(&self) {
+        let mut last_move = self.last_move.lock().unwrap();
+        let now = Instant::now();
+        if last_move.is_some_and(|t| now.duration_since(t) < MOVE_DEBOUNCE) {
+            return;
+        }
+        *last_move = Some(now);
+        drop(last_move);
+        self.trigger(self.menu_move.as_ref());
+    }
+
+    pub fn play_menu_select(&self) {
+        self.trigger(self.menu_select.as_ref());
+    }
+
+    pub fn play_menu_back(&self) {
+        self.trigger(self.menu_back.as_ref());
+    }
+
+    fn trigger(&self, cue: Option<&Cue>) {
+        let Some(cue) = cue else { return };
+        self.state.lock().unwrap().voices.push(Voice { cue: cue.clone(), pos: 0 });
+    }
+}
+
+/// Mix all active voices into `out` (interleaved f32, `channels` per frame),
+/// dropping voices that have finished.
+fn mix_into(state: &Arc<Mutex<MixerState>>, out: &mut [f32], channels: u16) {
+    for s in out.iter_mut() {
+        *s = 0.0;
+    }
+    let mut state = state.lock().unwrap();
+    let volume = state.volume;
+    let channels = channels as usize;
+    state.voices.retain_mut(|voice| {
+        for frame in out.chunks_mut(channels) {
+            if voice.pos >= voice.cue.samples.len() {
+                return false;
+            }
+            let s = (voice.cue.samples[voice.pos] as f32 / i16::MAX as f32) * volume;
+            for ch in frame.iter_mut() {
+                *ch += s;
+            }
+            voice.pos += 1;
+        }
+        voice.pos < voice.cue.samples.len()
+    });
+}
+
+/// Decode a WAV or Ogg Vorbis file into mono PCM at `target_rate`.
+fn decode_cue(path: &Path, target_rate: u32) -> Result<Cue, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("read {}: {e}", path.display()))?;
+
+    let (samples, channels, rate) = if bytes.starts_with(b"RIFF") {
+        decode_wav(&bytes)?
+    } else if bytes.starts_with(b"OggS") {
+        decode_vorbis(&bytes)?
+    } else {
+        return Err("unrecognized audio format (expected RIFF/WAV or OggS/Vorbis)".into());
+    };
+
+    let mono = downmix_to_mono(&samples, channels);
+    let resampled = resample_nearest(&mono, rate, target_rate);
+    Ok(Cue { samples: resampled.into() })
+}
+
+/// Minimal WAV parser: walks RIFF chunks looking for `fmt ` and `data`.
+/// Supports 16-bit PCM only (the only format our cue assets use).
+fn decode_wav(bytes: &[u8]) -> Result<(Vec<i16>, u16, u32), String> {
+    if bytes.len() < 12 || &bytes[8..12] != b"WAVE" {
+        return Err("not a WAVE file".into());
+    }
+
+    let mut pos = 12;
+    let mut channels = 1u16;
+    let mut sample_rate = 44100u32;
+    let mut bits_per_sample = 16u16;
+    let mut data: Option<&[u8]> = None;
+
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = u32::from_le_bytes(bytes[pos + 4..pos + 8].try_into().unwrap()) as usize;
+        let body_start = pos + 8;
+        let body_end = (body_start + size).min(bytes.len());
+        let body = &bytes[body_start..body_end];
+
+        match id {
+            b"fmt " => {
+                if body.len() < 16 {
+                    return Err("truncated fmt chunk".into());
+                }
+                channels = u16::from_le_bytes(body[2..4].try_into().unwrap());
+                sample_rate = u32::from_le_bytes(body[4..8].try_into().unwrap());
+                bits_per_sample = u16::from_le_bytes(body[14..16].try_into().unwrap());
+            }
+            b"data" => data = Some(body),
+            _ => {}
+        }
+
+        // Chunks are word-aligned.
+        pos = body_start + size + (size & 1);
+    }
+
+    if bits_per_sample != 16 {
+        return Err(format!("unsupported bit depth: {bits_per_sample}"));
+    }
+    let data = data.ok_or("missing data chunk")?;
+
+    let samples = data
+        .chunks_exact(2)
+        .map(|c| i16::from_le_bytes([c[0], c[1]]))
+        .collect();
+
+    Ok((samples, channels, sample_rate))
+}
+
+/// Decode an Ogg Vorbis stream into interleaved i16 PCM via `lewton`.
+fn decode_vorbis(bytes: &[u8]) -> Result<(Vec<i16>, u16, u32), String> {
+    use lewton::inside_ogg::OggStreamReader;
+
+    let mut reader = OggStreamReader::new(std::io::Cursor::new(bytes)).map_err(|e| e.to_string())?;
+    let channels = reader.ident_hdr.audio_channels as u16;
+    let sample_rate = reader.ident_hdr.audio_sample_rate;
+
+    let mut samples = Vec::new();
+    while let Some(packet) = reader.read_dec_packet_itl().map_err(|e| e.to_string())? {
+        samples.extend_from_slice(&packet);
+    }
+
+    Ok((samples, channels, sample_rate))
+}
+
+/// Average all channels of interleaved PCM down to mono.
+fn downmix_to_mono(samples: &[i16], channels: u16) -> Vec<i16> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+    let channels = channels as usize;
+    samples
+        .chunks(channels)
+        .map(|frame| (frame.iter().map(|&s| s as i32).sum::<i32>() / frame.len() as i32) as i16)
+        .collect()
+}
+
+/// Nearest-neighbor resample — good enough for short UI cues.
+fn resample_nearest(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = ((samples.len() as f64) / ratio).round() as usize;
+    (0..out_len)
+        .map(|i| {
+            let src_idx = ((i as f64) * ratio) as usize;
+            samples[src_idx.min(samples.len() - 1)]
+        })
+        .collect()
+}
@@ -0,0 +1,93 @@
+//! Backend-agnostic presentation surface.
+//!
+//! `Renderer::render_frame` (and friends) already produce a flat premultiplied
+//! ARGB `Vec<u32>` with no knowledge of the windowing system underneath it.
+//! `OverlaySurface` is the seam between that software-rendered buffer and
+//! whatever compositor protocol actually gets it on screen, so the drawing
+//! code never needs to know whether it's running under X11 or Wayland.
+
+use log::info;
+
+use crate::wayland::WaylandSurface;
+use crate::window::OverlayWindow;
+
+/// A windowing backend capable of displaying an ARGB overlay buffer.
+pub trait OverlaySurface {
+    /// Upload and display a new frame. `pixels` must be `width * height` long,
+    /// using the surface's current size: `screen_size()` after
+    /// [`create_surface`] (the X11 backend sizes itself to the active
+    /// monitor, not the requested size) or the most recent
+    /// [`resize`](OverlaySurface::resize).
+    fn present(&mut self, pixels: &[u32]);
+
+    /// Resize the surface. Backends should no-op if the size is unchanged.
+    fn resize(&mut self, width: u16, height: u16);
+
+    fn show(&mut self);
+    fn hide(&mut self);
+
+    /// Pump backend-native events (expose/configure notifications, etc.).
+    fn poll_events(&mut self);
+
+    fn screen_size(&self) -> (u16, u16);
+
+    /// Whether this backend can claim exclusive keyboard/controller focus.
+    /// Neither backend does today — both leave keyboard focus where it is,
+    /// and the overlay is driven by the Unix socket, D-Bus and its own
+    /// gamepad polling instead — but this lets a future input-grabbing
+    /// feature ask before it tries.
+    fn supports_input_grab(&self) -> bool;
+
+    /// Set whether the surface passes pointer/touch input through to
+    /// whatever's beneath it (click-through), rather than intercepting it.
+    /// A no-op default for backends that can't express a partial input
+    /// region — today only the X11 backend (via the XShape extension) does.
+    fn set_click_through(&mut self, enabled: bool) {
+        let _ = enabled;
+    }
+}
+
+/// Which backend to use, resolved from config/env at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    X11,
+    Wayland,
+}
+
+impl Backend {
+    /// Pick a backend: an explicit `SUPERKONNA_BACKEND` env var wins
+    /// (`x11` or `wayland`), otherwise auto-detect from `WAYLAND_DISPLAY`.
+    pub fn detect() -> Self {
+        match std::env::var("SUPERKONNA_BACKEND").as_deref() {
+            Ok("wayland") => return Backend::Wayland,
+            Ok("x11") => return Backend::X11,
+            _ => {}
+        }
+
+        if std::env::var("WAYLAND_DISPLAY").is_ok() {
+            Backend::Wayland
+        } else {
+            Backend::X11
+        }
+    }
+}
+
+/// Create the overlay surface for the detected (or requested) backend.
+pub fn create_surface(width: u16, height: u16) -> Result<Box<dyn OverlaySurface>, String> {
+    create_surface_with(Backend::detect(), width, height)
+}
+
+fn create_surface_with(backend: Backend, width: u16, height: u16) -> Result<Box<dyn OverlaySurface>, String> {
+    match backend {
+        Backend::X11 => {
+            info!("Using X11 override-redirect backend");
+            let win = OverlayWindow::new(width, height)?;
+            Ok(Box::new(win))
+        }
+        Backend::Wayland => {
+            info!("Using Wayland wlr-layer-shell backend");
+            let surf = WaylandSurface::new(width, height)?;
+            Ok(Box::new(surf))
+        }
+    }
+}
@@ -18,6 +18,90 @@ pub struct Theme {
     pub font_display_path: PathBuf,
     pub font_path: PathBuf,
     pub font_light_path: PathBuf,
+    pub font_styles: FontStyles,
+}
+
+/// Which loaded font family a [`FontStyle`] draws with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontFamily {
+    Display,
+    Body,
+    Light,
+}
+
+/// Which theme color a [`FontStyle`] draws with by default. Call sites that
+/// need a state-dependent color (e.g. a menu item swapping to `onMainColor`
+/// while selected) still override it explicitly — this is just the color a
+/// style draws with when nothing else is going on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorRole {
+    Fg,
+    Accent,
+    Subtle,
+}
+
+/// A named typography role: font family + size + base color, with optional
+/// letter-spacing for tracked-out labels (e.g. all-caps headers). Sizes are
+/// unscaled logical pixels — callers multiply by the same device/menu scale
+/// as any other layout constant.
+#[derive(Debug, Clone, Copy)]
+pub struct FontStyle {
+    pub family: FontFamily,
+    pub size: f32,
+    pub color: ColorRole,
+    pub letter_spacing: f32,
+}
+
+impl FontStyle {
+    const fn new(family: FontFamily, size: f32, color: ColorRole) -> Self {
+        FontStyle { family, size, color, letter_spacing: 0.0 }
+    }
+}
+
+/// Registry of named font styles used throughout the overlay, resolved once
+/// from the theme at load time so a theme author can retune typography (or
+/// later, per-theme density/weight presets) without touching renderer code.
+#[derive(Debug, Clone, Copy)]
+pub struct FontStyles {
+    pub toast_header: FontStyle,
+    pub toast_title: FontStyle,
+    pub toast_desc: FontStyle,
+    pub menu_item: FontStyle,
+    pub status_text: FontStyle,
+    pub hint: FontStyle,
+    pub tracker_label: FontStyle,
+    pub tracker_value: FontStyle,
+}
+
+impl FontStyles {
+    fn defaults() -> Self {
+        FontStyles {
+            toast_header: FontStyle::new(FontFamily::Body, 9.5, ColorRole::Accent),
+            toast_title: FontStyle::new(FontFamily::Display, 16.0, ColorRole::Fg),
+            toast_desc: FontStyle::new(FontFamily::Light, 11.5, ColorRole::Subtle),
+            menu_item: FontStyle::new(FontFamily::Body, 15.0, ColorRole::Fg),
+            status_text: FontStyle::new(FontFamily::Light, 12.0, ColorRole::Fg),
+            hint: FontStyle::new(FontFamily::Light, 10.0, ColorRole::Fg),
+            tracker_label: FontStyle::new(FontFamily::Body, 11.0, ColorRole::Fg),
+            tracker_value: FontStyle::new(FontFamily::Light, 13.0, ColorRole::Accent),
+        }
+    }
+
+    /// Layer `variables.xml` size overrides (`fontSize<Role>`) on top of the
+    /// built-in defaults. Missing or unparsable keys keep the default.
+    fn load(vars: &HashMap<String, String>) -> Self {
+        let mut styles = Self::defaults();
+        let size = |key: &str| vars.get(key).and_then(|v| v.parse::<f32>().ok());
+        if let Some(v) = size("fontSizeToastHeader") { styles.toast_header.size = v; }
+        if let Some(v) = size("fontSizeToastTitle") { styles.toast_title.size = v; }
+        if let Some(v) = size("fontSizeToastDesc") { styles.toast_desc.size = v; }
+        if let Some(v) = size("fontSizeMenuItem") { styles.menu_item.size = v; }
+        if let Some(v) = size("fontSizeStatusText") { styles.status_text.size = v; }
+        if let Some(v) = size("fontSizeHint") { styles.hint.size = v; }
+        if let Some(v) = size("fontSizeTrackerLabel") { styles.tracker_label.size = v; }
+        if let Some(v) = size("fontSizeTrackerValue") { styles.tracker_value.size = v; }
+        styles
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -101,6 +185,7 @@ impl Theme {
             font_display_path: resolve_font("fontDisplay", "assets/fonts/Inter/Inter-Bold.otf"),
             font_path: resolve_font("fontBody", "assets/fonts/Inter/Inter-Regular.otf"),
             font_light_path: resolve_font("fontLight", "assets/fonts/Inter/Inter-Light.otf"),
+            font_styles: FontStyles::load(&vars),
         })
     }
 }
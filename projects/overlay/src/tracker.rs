@@ -0,0 +1,91 @@
+//! Live leaderboard tracker state — RetroArch's `active_lboard_trackers`
+//! update continuously while a timed or score challenge is in progress.
+//! `TrackerSet` mirrors that as an `id -> (name, value)` map so the renderer
+//! can draw a small stack of rows independent of the popup queue and menu.
+
+use std::collections::BTreeMap;
+
+/// One active leaderboard attempt: its display name and current value text
+/// (e.g. a running clock or score), updated in place every time RetroArch
+/// reports a new value — no open/close animation to retrigger.
+#[derive(Debug, Clone)]
+pub struct Tracker {
+    pub name: String,
+    pub value: String,
+}
+
+/// Holds the trackers currently on screen, keyed by leaderboard id.
+#[derive(Debug, Default)]
+pub struct TrackerSet {
+    trackers: BTreeMap<String, Tracker>,
+}
+
+impl TrackerSet {
+    pub fn new() -> Self {
+        TrackerSet::default()
+    }
+
+    /// A leaderboard attempt began — show a row for it with an empty value
+    /// until the first per-frame update arrives.
+    pub fn start(&mut self, id: String, name: String) {
+        self.trackers.insert(id, Tracker { name, value: String::new() });
+    }
+
+    /// RetroArch reported a fresh value for an in-progress attempt.
+    pub fn update_value(&mut self, id: &str, value: String) {
+        if let Some(tracker) = self.trackers.get_mut(id) {
+            tracker.value = value;
+        }
+    }
+
+    /// The attempt ended (submitted or canceled) — drop its row.
+    pub fn remove(&mut self, id: &str) {
+        self.trackers.remove(id);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trackers.is_empty()
+    }
+
+    /// Rows to draw, in a stable order (ascending by id).
+    pub fn rows(&self) -> impl Iterator<Item = &Tracker> {
+        self.trackers.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_then_update_then_remove() {
+        let mut set = TrackerSet::new();
+        assert!(set.is_empty());
+
+        set.start("5".into(), "Speed Run".into());
+        assert_eq!(set.rows().count(), 1);
+        assert_eq!(set.rows().next().unwrap().value, "");
+
+        set.update_value("5", "00:12.34".into());
+        assert_eq!(set.rows().next().unwrap().value, "00:12.34");
+
+        set.remove("5");
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn update_for_unknown_id_is_ignored() {
+        let mut set = TrackerSet::new();
+        set.update_value("nope", "99".into());
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn rows_are_ordered_by_id() {
+        let mut set = TrackerSet::new();
+        set.start("9".into(), "Second".into());
+        set.start("1".into(), "First".into());
+        let names: Vec<&str> = set.rows().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["First", "Second"]);
+    }
+}
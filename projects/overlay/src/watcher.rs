@@ -3,6 +3,7 @@
 
 use std::fs::File;
 use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::os::unix::fs::MetadataExt;
 use std::path::Path;
 use std::sync::mpsc::Sender;
 use std::time::Duration;
@@ -10,10 +11,30 @@ use std::time::Duration;
 use log::{debug, error, info, warn};
 use notify::{EventKind, RecursiveMode, Watcher};
 
-#[derive(Debug, Clone)]
-pub struct AchievementEvent {
-    pub title: String,
-    pub description: String,
+/// Consecutive notify wake-ups that read zero new bytes before we suspect
+/// the path was replaced without a `Remove` event reaching us (some
+/// filesystems coalesce a fast truncate+rewrite into a single `Modify`).
+const STALE_REOPEN_THRESHOLD: u32 = 3;
+
+/// One RetroAchievements event parsed from a `[RCHEEVOS]:` log line.
+/// `Unlock` is the common case; the rest cover mastery, leaderboards,
+/// challenge indicators, and login — previously dropped on the floor
+/// because `parse_cheevo_line` only recognized the award pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AchievementEvent {
+    Unlock { id: String, title: String, description: String, points: Option<u32> },
+    Mastery { game: String, hardcore: bool },
+    LeaderboardStarted { id: String, name: String },
+    /// A per-frame value update for an in-progress attempt (e.g. a running
+    /// clock or score), emitted continuously while `tracker::TrackerSet`
+    /// shows a row for `id`.
+    LeaderboardUpdated { id: String, value: String },
+    LeaderboardSubmitted { id: String, name: String, value: String },
+    LeaderboardCanceled { id: String, name: String },
+    ChallengeShown { id: String },
+    ChallengeHidden { id: String },
+    LoginSucceeded,
+    ProgressIndicator { id: String, current: String, target: String },
 }
 
 /// Watch the RetroArch log file for achievement events.
@@ -25,11 +46,9 @@ pub fn watch_log(path: &Path, tx: Sender<AchievementEvent>) -> Result<(), String
         std::thread::sleep(Duration::from_secs(2));
     }
 
-    let mut file = File::open(path).map_err(|e| format!("open log: {e}"))?;
-    // Seek to end — only process new lines
-    file.seek(SeekFrom::End(0)).map_err(|e| format!("seek: {e}"))?;
-    let mut reader = BufReader::new(file);
+    let (mut reader, mut cur_ino) = open_log(path, false)?;
     let mut line_buf = String::new();
+    let mut stale_reads: u32 = 0;
 
     // Set up file watcher
     let (notify_tx, notify_rx) = std::sync::mpsc::channel();
@@ -50,6 +69,22 @@ pub fn watch_log(path: &Path, tx: Sender<AchievementEvent>) -> Result<(), String
         // Wait for file change notification
         match notify_rx.recv_timeout(Duration::from_secs(5)) {
             Ok(event) => {
+                if matches!(event.kind, EventKind::Remove(_)) {
+                    // Batocera rotates /tmp/retroarch.log on every emulator
+                    // launch — the old inode is gone, so re-arm the watch on
+                    // the (new) path and start reading the replacement fresh.
+                    info!("Log file removed — waiting for replacement");
+                    let _ = watcher.unwatch(path);
+                    while !path.exists() {
+                        std::thread::sleep(Duration::from_millis(200));
+                    }
+                    if let Err(e) = watcher.watch(path, RecursiveMode::NonRecursive) {
+                        warn!("Re-watch failed: {e}");
+                    }
+                    (reader, cur_ino) = open_log(path, true)?;
+                    stale_reads = 0;
+                    continue;
+                }
                 if !matches!(event.kind, EventKind::Modify(_)) {
                     continue;
                 }
@@ -60,12 +95,24 @@ pub fn watch_log(path: &Path, tx: Sender<AchievementEvent>) -> Result<(), String
             }
         }
 
+        // Truncation/rotation-in-place: RetroArch reopens the same path
+        // with O_TRUNC, so our saved offset can land past the new EOF.
+        if let Ok(meta) = std::fs::metadata(path) {
+            if meta.ino() == cur_ino && meta.len() < reader.stream_position().unwrap_or(0) {
+                info!("Log file truncated — reopening from start");
+                (reader, cur_ino) = open_log(path, true)?;
+                stale_reads = 0;
+            }
+        }
+
         // Read new lines
+        let mut read_any = false;
         loop {
             line_buf.clear();
             match reader.read_line(&mut line_buf) {
                 Ok(0) => break, // No more data
                 Ok(_) => {
+                    read_any = true;
                     if let Some(event) = parse_cheevo_line(&line_buf) {
                         if tx.send(event).is_err() {
                             return Err("channel closed".to_string());
@@ -78,36 +125,125 @@ pub fn watch_log(path: &Path, tx: Sender<AchievementEvent>) -> Result<(), String
                 }
             }
         }
+
+        if read_any {
+            stale_reads = 0;
+            continue;
+        }
+
+        // Woken up but nothing new to read, several times in a row — check
+        // whether the path now points at a different inode (a rotation that
+        // `notify` reported as a plain `Modify` rather than `Remove`) and, if
+        // so, pick up the replacement from the start.
+        stale_reads += 1;
+        if stale_reads >= STALE_REOPEN_THRESHOLD {
+            stale_reads = 0;
+            match std::fs::metadata(path) {
+                Ok(meta) if meta.ino() != cur_ino => {
+                    info!("Log file replaced at same path — reopening from start");
+                    (reader, cur_ino) = open_log(path, true)?;
+                }
+                _ => {}
+            }
+        }
     }
 }
 
-/// Parse a RetroArch log line for achievement unlock events.
-/// Format: `[INFO] [RCHEEVOS]: awarding cheevo <ID>: <Name> (<Description>)`
+/// Open `path` for tailing, returning the reader and its inode. Seeks to EOF
+/// unless `from_start` (used when resuming after a detected rotation, where
+/// we want whatever the replacement file already has).
+fn open_log(path: &Path, from_start: bool) -> Result<(BufReader<File>, u64), String> {
+    let mut file = File::open(path).map_err(|e| format!("open log: {e}"))?;
+    let ino = file.metadata().map_err(|e| format!("stat log: {e}"))?.ino();
+    if !from_start {
+        file.seek(SeekFrom::End(0)).map_err(|e| format!("seek: {e}"))?;
+    }
+    Ok((BufReader::new(file), ino))
+}
+
+/// Parse a RetroArch log line into an `AchievementEvent`, if it's one we
+/// recognize. Scans for the `[RCHEEVOS]:` prefix, then branches on the
+/// marker that follows: `awarding cheevo` (unlock), `mastered`/`completed`
+/// (mastery), `lboard` (leaderboard), or `login` (login success).
 fn parse_cheevo_line(line: &str) -> Option<AchievementEvent> {
-    // Look for the RCHEEVOS award pattern
-    let marker = "[RCHEEVOS]: awarding cheevo";
+    let marker = "[RCHEEVOS]:";
     let idx = line.find(marker)?;
-    let after = &line[idx + marker.len()..];
-
-    // Skip the ID: find the first `: ` after the number
-    let colon_idx = after.find(": ")?;
-    let rest = &after[colon_idx + 2..].trim();
-
-    // Split title and description at ` (`
-    if let Some(paren_idx) = rest.find(" (") {
-        let title = rest[..paren_idx].trim().to_string();
-        let desc_end = rest.rfind(')')?;
-        let description = rest[paren_idx + 2..desc_end].trim().to_string();
-        Some(AchievementEvent { title, description })
+    let after = line[idx + marker.len()..].trim();
+
+    if let Some(rest) = after.strip_prefix("awarding cheevo") {
+        return parse_unlock(rest);
+    }
+    if after.contains("mastered") || after.contains("completed") {
+        return parse_mastery(after);
+    }
+    if let Some(rest) = after.strip_prefix("lboard") {
+        return parse_leaderboard(rest);
+    }
+    if after.contains("login") {
+        return Some(AchievementEvent::LoginSucceeded);
+    }
+    None
+}
+
+/// Parse the text after `awarding cheevo`: ` <ID>: <Name> (<Description>)`.
+fn parse_unlock(rest: &str) -> Option<AchievementEvent> {
+    let colon_idx = rest.find(": ")?;
+    let id = rest[..colon_idx].trim().to_string();
+    let body = rest[colon_idx + 2..].trim();
+
+    if let Some(paren_idx) = body.find(" (") {
+        let title = body[..paren_idx].trim().to_string();
+        let desc_end = body.rfind(')')?;
+        let description = body[paren_idx + 2..desc_end].trim().to_string();
+        Some(AchievementEvent::Unlock { id, title, description, points: None })
     } else {
         // No description in parentheses — use the whole thing as title
-        Some(AchievementEvent {
-            title: rest.trim_end().to_string(),
+        Some(AchievementEvent::Unlock {
+            id,
+            title: body.trim_end().to_string(),
             description: String::new(),
+            points: None,
         })
     }
 }
 
+/// Parse a `mastered`/`completed` line: `[hardcore] mastered|completed <game>`.
+fn parse_mastery(after: &str) -> Option<AchievementEvent> {
+    let hardcore = after.contains("hardcore");
+    let keyword = if after.contains("mastered") { "mastered" } else { "completed" };
+    let idx = after.find(keyword)?;
+    let game = after[idx + keyword.len()..].trim().to_string();
+    if game.is_empty() {
+        return None;
+    }
+    Some(AchievementEvent::Mastery { game, hardcore })
+}
+
+/// Parse the text after `lboard`: ` <ID>: <Name> started|submitted <value>|canceled`,
+/// or the per-frame update shorthand ` <ID>: value <value>`.
+fn parse_leaderboard(rest: &str) -> Option<AchievementEvent> {
+    let rest = rest.trim_start();
+    let colon_idx = rest.find(':')?;
+    let id = rest[..colon_idx].trim().to_string();
+    let after_id = rest[colon_idx + 1..].trim();
+
+    if let Some(value) = after_id.strip_prefix("value ") {
+        return Some(AchievementEvent::LeaderboardUpdated { id, value: value.trim().to_string() });
+    }
+    if let Some(name) = after_id.strip_suffix("started") {
+        return Some(AchievementEvent::LeaderboardStarted { id, name: name.trim().to_string() });
+    }
+    if let Some(name) = after_id.strip_suffix("canceled") {
+        return Some(AchievementEvent::LeaderboardCanceled { id, name: name.trim().to_string() });
+    }
+    if let Some(submitted_idx) = after_id.find("submitted") {
+        let name = after_id[..submitted_idx].trim().to_string();
+        let value = after_id[submitted_idx + "submitted".len()..].trim().to_string();
+        return Some(AchievementEvent::LeaderboardSubmitted { id, name, value });
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,21 +252,81 @@ mod tests {
     fn parse_standard_cheevo() {
         let line = "[INFO] [RCHEEVOS]: awarding cheevo 12345: First Blood (Defeat the first boss)";
         let event = parse_cheevo_line(line).unwrap();
-        assert_eq!(event.title, "First Blood");
-        assert_eq!(event.description, "Defeat the first boss");
+        assert_eq!(event, AchievementEvent::Unlock {
+            id: "12345".into(),
+            title: "First Blood".into(),
+            description: "Defeat the first boss".into(),
+            points: None,
+        });
     }
 
     #[test]
     fn parse_cheevo_no_description() {
         let line = "[INFO] [RCHEEVOS]: awarding cheevo 99: Welcome";
         let event = parse_cheevo_line(line).unwrap();
-        assert_eq!(event.title, "Welcome");
-        assert_eq!(event.description, "");
+        assert_eq!(event, AchievementEvent::Unlock {
+            id: "99".into(),
+            title: "Welcome".into(),
+            description: String::new(),
+            points: None,
+        });
+    }
+
+    #[test]
+    fn parse_softcore_mastery() {
+        let line = "[INFO] [RCHEEVOS]: completed Super Mario World";
+        let event = parse_cheevo_line(line).unwrap();
+        assert_eq!(event, AchievementEvent::Mastery { game: "Super Mario World".into(), hardcore: false });
+    }
+
+    #[test]
+    fn parse_hardcore_mastery() {
+        let line = "[INFO] [RCHEEVOS]: hardcore mastered Super Mario World";
+        let event = parse_cheevo_line(line).unwrap();
+        assert_eq!(event, AchievementEvent::Mastery { game: "Super Mario World".into(), hardcore: true });
+    }
+
+    #[test]
+    fn parse_leaderboard_started() {
+        let line = "[INFO] [RCHEEVOS]: lboard 5: Speed Run started";
+        let event = parse_cheevo_line(line).unwrap();
+        assert_eq!(event, AchievementEvent::LeaderboardStarted { id: "5".into(), name: "Speed Run".into() });
+    }
+
+    #[test]
+    fn parse_leaderboard_value_update() {
+        let line = "[INFO] [RCHEEVOS]: lboard 5: value 00:12.34";
+        let event = parse_cheevo_line(line).unwrap();
+        assert_eq!(event, AchievementEvent::LeaderboardUpdated { id: "5".into(), value: "00:12.34".into() });
+    }
+
+    #[test]
+    fn parse_leaderboard_submitted() {
+        let line = "[INFO] [RCHEEVOS]: lboard 5: Speed Run submitted 01:23.45";
+        let event = parse_cheevo_line(line).unwrap();
+        assert_eq!(event, AchievementEvent::LeaderboardSubmitted {
+            id: "5".into(),
+            name: "Speed Run".into(),
+            value: "01:23.45".into(),
+        });
+    }
+
+    #[test]
+    fn parse_leaderboard_canceled() {
+        let line = "[INFO] [RCHEEVOS]: lboard 5: Speed Run canceled";
+        let event = parse_cheevo_line(line).unwrap();
+        assert_eq!(event, AchievementEvent::LeaderboardCanceled { id: "5".into(), name: "Speed Run".into() });
     }
 
     #[test]
-    fn ignore_non_cheevo_line() {
+    fn parse_login_succeeded() {
         let line = "[INFO] [RCHEEVOS]: login succeeded";
+        assert_eq!(parse_cheevo_line(line), Some(AchievementEvent::LoginSucceeded));
+    }
+
+    #[test]
+    fn ignore_unrelated_line() {
+        let line = "[INFO] [RCHEEVOS]: something we don't recognize";
         assert!(parse_cheevo_line(line).is_none());
     }
 }
@@ -0,0 +1,222 @@
+//! Wayland overlay surface using the `wlr-layer-shell` protocol.
+//!
+//! Renders the same premultiplied ARGB buffer the X11 backend does, just
+//! through an `wlr_layer_surface_v1` anchored to all four screen edges on
+//! the `Overlay` layer instead of an override-redirect window. Compositors
+//! that don't speak `wlr-layer-shell` (GNOME, most KDE sessions) aren't
+//! supported here — [`Backend::detect`](crate::surface::Backend::detect)
+//! selects this backend whenever `WAYLAND_DISPLAY` is set (or it is asked
+//! for explicitly), and on such a compositor `WaylandSurface::new` fails
+//! with a missing `zwlr_layer_shell_v1` global.
+
+use log::debug;
+use wayland_client::protocol::{wl_compositor, wl_shm, wl_shm_pool, wl_surface};
+use wayland_client::{Connection, Dispatch, EventQueue, QueueHandle};
+use wayland_protocols_wlr::layer_shell::v1::client::{zwlr_layer_shell_v1, zwlr_layer_surface_v1};
+
+use crate::surface::OverlaySurface;
+
+pub struct WaylandSurface {
+    conn: Connection,
+    /// The same queue `compositor`/`surface`/`layer_surface` were created
+    /// against in `new()` — every object only ever receives events on the
+    /// queue it was bound with, so this must be reused, never replaced.
+    event_queue: EventQueue<State>,
+    queue_handle: QueueHandle<State>,
+    state: State,
+    current_width: u16,
+    current_height: u16,
+    visible: bool,
+}
+
+struct State {
+    compositor: Option<wl_compositor::WlCompositor>,
+    shm: Option<wl_shm::WlShm>,
+    layer_shell: Option<zwlr_layer_shell_v1::ZwlrLayerShellV1>,
+    surface: Option<wl_surface::WlSurface>,
+    layer_surface: Option<zwlr_layer_surface_v1::ZwlrLayerSurfaceV1>,
+    configured: bool,
+}
+
+impl WaylandSurface {
+    pub fn new(width: u16, height: u16) -> Result<Self, String> {
+        let conn = Connection::connect_to_env().map_err(|e| format!("wayland connect: {e}"))?;
+        let display = conn.display();
+        let mut event_queue = conn.new_event_queue();
+        let qh = event_queue.handle();
+
+        let mut state = State {
+            compositor: None,
+            shm: None,
+            layer_shell: None,
+            surface: None,
+            layer_surface: None,
+            configured: false,
+        };
+
+        let _registry = display.get_registry(&qh, ());
+        event_queue.roundtrip(&mut state).map_err(|e| format!("wayland roundtrip: {e}"))?;
+
+        let compositor = state.compositor.clone().ok_or("compositor global missing")?;
+        let layer_shell = state.layer_shell.clone().ok_or("zwlr_layer_shell_v1 global missing (compositor has no wlr-layer-shell support)")?;
+
+        let surface = compositor.create_surface(&qh, ());
+        let layer_surface = layer_shell.get_layer_surface(
+            &surface,
+            None,
+            zwlr_layer_shell_v1::Layer::Overlay,
+            "superkonna-overlay".to_string(),
+            &qh,
+            (),
+        );
+        layer_surface.set_anchor(
+            zwlr_layer_surface_v1::Anchor::Top
+                | zwlr_layer_surface_v1::Anchor::Bottom
+                | zwlr_layer_surface_v1::Anchor::Left
+                | zwlr_layer_surface_v1::Anchor::Right,
+        );
+        layer_surface.set_size(width as u32, height as u32);
+        layer_surface.set_exclusive_zone(-1);
+        layer_surface.set_keyboard_interactivity(zwlr_layer_surface_v1::KeyboardInteractivity::None);
+        surface.commit();
+
+        state.surface = Some(surface);
+        state.layer_surface = Some(layer_surface);
+
+        // Wait for the compositor's initial configure before the first present.
+        while !state.configured {
+            event_queue.blocking_dispatch(&mut state).map_err(|e| format!("wayland dispatch: {e}"))?;
+        }
+
+        debug!("Wayland layer-shell surface created: {width}x{height}");
+
+        Ok(WaylandSurface {
+            conn,
+            event_queue,
+            queue_handle: qh,
+            state,
+            current_width: width,
+            current_height: height,
+            visible: false,
+        })
+    }
+}
+
+impl OverlaySurface for WaylandSurface {
+    fn present(&mut self, pixels: &[u32]) {
+        let Some(surface) = &self.state.surface else { return };
+        let Some(shm) = &self.state.shm else { return };
+
+        let (w, h) = (self.current_width as i32, self.current_height as i32);
+        let stride = w * 4;
+        let size = (stride * h) as usize;
+
+        let mut file = match tempfile::tempfile() {
+            Ok(f) => f,
+            Err(e) => {
+                debug!("Wayland shm tempfile failed: {e}");
+                return;
+            }
+        };
+        use std::io::Write;
+        let mut bytes = Vec::with_capacity(size);
+        for &px in pixels {
+            bytes.extend_from_slice(&px.to_le_bytes());
+        }
+        if file.write_all(&bytes).is_err() {
+            return;
+        }
+
+        let pool = shm.create_pool(std::os::fd::AsFd::as_fd(&file), size as i32, &self.queue_handle, ());
+        let buffer = pool.create_buffer(0, w, h, stride, wl_shm::Format::Argb8888, &self.queue_handle, ());
+        surface.attach(Some(&buffer), 0, 0);
+        surface.damage_buffer(0, 0, w, h);
+        surface.commit();
+        pool.destroy();
+
+        let _ = self.conn.flush();
+    }
+
+    fn resize(&mut self, width: u16, height: u16) {
+        if width == self.current_width && height == self.current_height {
+            return;
+        }
+        if let Some(layer_surface) = &self.state.layer_surface {
+            layer_surface.set_size(width as u32, height as u32);
+        }
+        if let Some(surface) = &self.state.surface {
+            surface.commit();
+        }
+        self.current_width = width;
+        self.current_height = height;
+    }
+
+    fn show(&mut self) {
+        self.visible = true;
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn poll_events(&mut self) {
+        let _ = self.event_queue.dispatch_pending(&mut self.state);
+    }
+
+    fn screen_size(&self) -> (u16, u16) {
+        (self.current_width, self.current_height)
+    }
+
+    fn supports_input_grab(&self) -> bool {
+        false
+    }
+}
+
+impl Dispatch<wayland_client::protocol::wl_registry::WlRegistry, ()> for State {
+    fn event(
+        state: &mut Self,
+        registry: &wayland_client::protocol::wl_registry::WlRegistry,
+        event: wayland_client::protocol::wl_registry::Event,
+        _data: &(),
+        _conn: &Connection,
+        qh: &QueueHandle<Self>,
+    ) {
+        if let wayland_client::protocol::wl_registry::Event::Global { name, interface, version } = event {
+            match interface.as_str() {
+                "wl_compositor" => {
+                    state.compositor = Some(registry.bind(name, version.min(4), qh, ()));
+                }
+                "wl_shm" => {
+                    state.shm = Some(registry.bind(name, version.min(1), qh, ()));
+                }
+                "zwlr_layer_shell_v1" => {
+                    state.layer_shell = Some(registry.bind(name, version.min(4), qh, ()));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Dispatch<zwlr_layer_surface_v1::ZwlrLayerSurfaceV1, ()> for State {
+    fn event(
+        state: &mut Self,
+        surface: &zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+        event: zwlr_layer_surface_v1::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        if let zwlr_layer_surface_v1::Event::Configure { serial, .. } = event {
+            surface.ack_configure(serial);
+            state.configured = true;
+        }
+    }
+}
+
+wayland_client::delegate_noop!(State: ignore wl_compositor::WlCompositor);
+wayland_client::delegate_noop!(State: ignore wl_surface::WlSurface);
+wayland_client::delegate_noop!(State: ignore wl_shm::WlShm);
+wayland_client::delegate_noop!(State: ignore wl_shm_pool::WlShmPool);
+wayland_client::delegate_noop!(State: ignore wayland_client::protocol::wl_buffer::WlBuffer);
+wayland_client::delegate_noop!(State: ignore zwlr_layer_shell_v1::ZwlrLayerShellV1);
@@ -1,8 +1,14 @@
 //! X11 overlay window using override-redirect for always-on-top without WM interaction.
 //! Uses 32-bit ARGB visual for transparency. Supports runtime resize/reposition
 //! for switching between popup (small, top-right) and menu (full-screen) modes.
+//!
+//! Tracks RandR output geometry so placement follows the active monitor
+//! rather than the (possibly multi-head) root screen — important on docked
+//! handhelds and TV-out setups where the active output is an offset CRTC.
 
 use x11rb::connection::Connection;
+use x11rb::protocol::randr::{self, ConnectionExt as _};
+use x11rb::protocol::shape::{self, ConnectionExt as _};
 use x11rb::protocol::xproto::*;
 use x11rb::protocol::Event;
 use x11rb::rust_connection::RustConnection;
@@ -13,6 +19,7 @@ use log::debug;
 pub struct OverlayWindow {
     conn: RustConnection,
     window: Window,
+    root: Window,
     gc: Gcontext,
     visible: bool,
     current_width: u16,
@@ -21,25 +28,48 @@ pub struct OverlayWindow {
     current_y: i16,
     screen_width: u16,
     screen_height: u16,
+    /// Connected outputs' CRTC geometries `(x, y, width, height)`, as of the
+    /// last `new()` or RandR `ScreenChangeNotify`.
+    monitors: Vec<(i16, i16, u16, u16)>,
+    /// Index into `monitors` the overlay is currently placed on.
+    active_monitor: usize,
+    /// The last frame actually uploaded to the X server, for diffing against
+    /// the next `update_pixels` call. `None` forces a full upload (first
+    /// frame, or after `force_full_redraw`).
+    previous_frame: Option<Vec<u32>>,
+    /// Set by `force_full_redraw`; consumed by the next `update_pixels` call.
+    force_full: bool,
 }
 
 impl OverlayWindow {
     pub fn new(width: u16, height: u16) -> Result<Self, String> {
         let (conn, screen_num) = RustConnection::connect(None).map_err(|e| format!("X11 connect: {e}"))?;
         let screen = &conn.setup().roots[screen_num];
-        let screen_width = screen.width_in_pixels;
-        let screen_height = screen.height_in_pixels;
+        let root = screen.root;
+
+        // Ask for ScreenChangeNotify so poll_events can follow hotplug/resolution
+        // changes; harmless no-op if the server has no RandR.
+        let _ = conn.randr_select_input(root, randr::NotifyMask::SCREEN_CHANGE);
+
+        let mut monitors = query_monitors(&conn, root);
+        if monitors.is_empty() {
+            monitors.push((0, 0, screen.width_in_pixels, screen.height_in_pixels));
+        }
+        let active_monitor = primary_monitor_index(&conn, root, &monitors);
+        let (mon_x, mon_y, mon_w, mon_h) = monitors[active_monitor];
+        // Cover the active monitor's real geometry; the caller-requested
+        // size is only a last-resort fallback if RandR reports a degenerate one.
+        let (width, height) = if mon_w == 0 || mon_h == 0 { (width, height) } else { (mon_w, mon_h) };
 
         let (visual, depth) = find_argb_visual(screen).unwrap_or((screen.root_visual, screen.root_depth));
 
         let colormap = conn.generate_id().map_err(|e| e.to_string())?;
-        conn.create_colormap(ColormapAlloc::NONE, colormap, screen.root, visual)
+        conn.create_colormap(ColormapAlloc::NONE, colormap, root, visual)
             .map_err(|e| e.to_string())?;
 
         let window = conn.generate_id().map_err(|e| e.to_string())?;
-        // Position at origin for full-screen overlay; popup compositing handles placement
-        let x = 0_i16;
-        let y = 0_i16;
+        let x = mon_x;
+        let y = mon_y;
 
         let values = CreateWindowAux::new()
             .override_redirect(1)
@@ -48,7 +78,7 @@ impl OverlayWindow {
             .colormap(colormap)
             .event_mask(EventMask::EXPOSURE | EventMask::STRUCTURE_NOTIFY);
 
-        conn.create_window(depth, window, screen.root, x, y, width, height, 0, WindowClass::INPUT_OUTPUT, visual, &values)
+        conn.create_window(depth, window, root, x, y, width, height, 0, WindowClass::INPUT_OUTPUT, visual, &values)
             .map_err(|e| format!("create_window: {e}"))?;
 
         let atom_type = intern_atom(&conn, "_NET_WM_WINDOW_TYPE")?;
@@ -69,13 +99,15 @@ impl OverlayWindow {
         conn.create_gc(gc, window, &CreateGCAux::new()).map_err(|e| e.to_string())?;
         conn.flush().map_err(|e| e.to_string())?;
 
-        debug!("Window created: {width}x{height} at ({x},{y}), screen={screen_width}x{screen_height}");
+        debug!("Window created: {width}x{height} at ({x},{y}), monitor {active_monitor} of {}", monitors.len());
 
         Ok(OverlayWindow {
-            conn, window, gc, visible: false,
+            conn, window, root, gc, visible: false,
             current_width: width, current_height: height,
             current_x: x, current_y: y,
-            screen_width, screen_height,
+            screen_width: width, screen_height: height,
+            monitors, active_monitor,
+            previous_frame: None, force_full: false,
         })
     }
 
@@ -83,6 +115,33 @@ impl OverlayWindow {
         (self.screen_width, self.screen_height)
     }
 
+    /// The active monitor's geometry as `(x, y, width, height)`, in root
+    /// screen coordinates.
+    pub fn active_monitor_geometry(&self) -> (i16, i16, u16, u16) {
+        self.monitors[self.active_monitor]
+    }
+
+    /// Move the overlay to cover a different connected monitor by index
+    /// into the list implied by `active_monitor_geometry`'s ordering.
+    /// No-op if `idx` is out of range.
+    pub fn place_on_monitor(&mut self, idx: usize) {
+        let Some(&(x, y, w, h)) = self.monitors.get(idx) else { return };
+        self.active_monitor = idx;
+        self.screen_width = w;
+        self.screen_height = h;
+        self.current_x = x;
+        self.current_y = y;
+        self.current_width = w;
+        self.current_height = h;
+        let values = ConfigureWindowAux::new().x(x as i32).y(y as i32).width(w as u32).height(h as u32);
+        let _ = self.conn.configure_window(self.window, &values);
+        let _ = self.conn.flush();
+        // The window just moved to a different patch of screen — the cached
+        // `previous_frame` describes pixels that were never actually drawn
+        // at this new position.
+        self.force_full_redraw();
+    }
+
     /// Resize the window. Only sends X11 request if dimensions changed.
     pub fn resize(&mut self, width: u16, height: u16) {
         if width == self.current_width && height == self.current_height {
@@ -92,21 +151,30 @@ impl OverlayWindow {
         let _ = self.conn.configure_window(self.window, &values);
         self.current_width = width;
         self.current_height = height;
+        self.force_full_redraw();
     }
 
-    /// Reposition the window. Only sends X11 request if position changed.
+    /// Reposition the window, relative to the active monitor's origin.
+    /// Only sends X11 request if the resulting absolute position changed.
     pub fn reposition(&mut self, x: i16, y: i16) {
-        if x == self.current_x && y == self.current_y {
+        let (mon_x, mon_y, _, _) = self.active_monitor_geometry();
+        let (abs_x, abs_y) = (mon_x.saturating_add(x), mon_y.saturating_add(y));
+        if abs_x == self.current_x && abs_y == self.current_y {
             return;
         }
-        let values = ConfigureWindowAux::new().x(x as i32).y(y as i32);
+        let values = ConfigureWindowAux::new().x(abs_x as i32).y(abs_y as i32);
         let _ = self.conn.configure_window(self.window, &values);
-        self.current_x = x;
-        self.current_y = y;
+        self.current_x = abs_x;
+        self.current_y = abs_y;
     }
 
     pub fn show(&mut self) {
         if !self.visible {
+            // Backing content across an unmap/remap is undefined — without
+            // this, the dirty-rect diff in `update_pixels` compares against
+            // the stale `previous_frame` and can skip rows that look
+            // unchanged but were never actually repainted.
+            self.force_full_redraw();
             let _ = self.conn.map_window(self.window);
             let _ = self.conn.flush();
             self.visible = true;
@@ -121,27 +189,168 @@ impl OverlayWindow {
         }
     }
 
-    pub fn update_pixels(&self, pixels: &[u32], width: u16, height: u16) {
-        let mut data = Vec::with_capacity(pixels.len() * 4);
-        for &px in pixels {
-            data.push((px & 0xFF) as u8);
-            data.push(((px >> 8) & 0xFF) as u8);
-            data.push(((px >> 16) & 0xFF) as u8);
-            data.push(((px >> 24) & 0xFF) as u8);
+    /// Upload `pixels` to the window, only transmitting the sub-rectangles
+    /// that actually changed since the last call (diffed against the cached
+    /// `previous_frame`). Falls back to a single full-frame upload on the
+    /// first call, right after `force_full_redraw`, when the buffer size
+    /// changed, or when the dirty area covers more than 60% of the window
+    /// anyway — at that point per-rect overhead isn't worth it.
+    pub fn update_pixels(&mut self, pixels: &[u32], width: u16, height: u16) {
+        let same_size = self.previous_frame.as_ref().is_some_and(|p| p.len() == pixels.len());
+
+        if self.force_full || !same_size {
+            self.upload_rect(pixels, width, 0, 0, width, height);
+            self.previous_frame = Some(pixels.to_vec());
+            self.force_full = false;
+            return;
         }
 
-        let _ = self.conn.put_image(ImageFormat::Z_PIXMAP, self.window, self.gc, width, height, 0, 0, 0, 32, &data);
+        let prev = self.previous_frame.as_ref().unwrap();
+        let rects = dirty_rects(prev, pixels, width, height);
+        if rects.is_empty() {
+            return;
+        }
+
+        let dirty_area: u64 = rects.iter().map(|&(_, _, w, h)| w as u64 * h as u64).sum();
+        let total_area = width as u64 * height as u64;
+        if dirty_area * 100 > total_area * 60 {
+            self.upload_rect(pixels, width, 0, 0, width, height);
+        } else {
+            for (x, y, w, h) in rects {
+                self.upload_rect(pixels, width, x, y, w, h);
+            }
+        }
+        self.previous_frame = Some(pixels.to_vec());
+    }
+
+    /// Invalidate the cached frame buffer so the next `update_pixels` call
+    /// does a full-window upload instead of diffing. Call on Expose events
+    /// and resize, where the previous frame no longer reflects what's on
+    /// screen.
+    pub fn force_full_redraw(&mut self) {
+        self.force_full = true;
+    }
+
+    /// Convert and `put_image` a `width x height` sub-rectangle of `pixels`
+    /// (whose full row stride is `full_width`) at window offset `(x, y)`.
+    fn upload_rect(&self, pixels: &[u32], full_width: u16, x: u16, y: u16, width: u16, height: u16) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+        for row in 0..height as usize {
+            let row_start = (y as usize + row) * full_width as usize + x as usize;
+            for &px in &pixels[row_start..row_start + width as usize] {
+                data.push((px & 0xFF) as u8);
+                data.push(((px >> 8) & 0xFF) as u8);
+                data.push(((px >> 16) & 0xFF) as u8);
+                data.push(((px >> 24) & 0xFF) as u8);
+            }
+        }
+
+        let _ = self.conn.put_image(
+            ImageFormat::Z_PIXMAP, self.window, self.gc, width, height, x as i16, y as i16, 0, 32, &data,
+        );
         let _ = self.conn.flush();
     }
 
-    pub fn poll_events(&self) {
+    pub fn poll_events(&mut self) {
         while let Ok(Some(event)) = self.conn.poll_for_event() {
             match event {
-                Event::Expose(_) => debug!("Expose event"),
+                Event::Expose(_) => {
+                    debug!("Expose event");
+                    self.force_full_redraw();
+                }
+                Event::RandrScreenChangeNotify(_) => {
+                    debug!("RandR screen change notified, re-querying monitor geometry");
+                    self.refresh_monitors();
+                }
                 _ => {}
             }
         }
     }
+
+    /// Re-query connected outputs and their CRTC geometries, keeping the
+    /// active monitor index stable if it's still a valid index, and
+    /// reconfiguring the live window onto that monitor's (possibly changed)
+    /// geometry — a hotplug can resize or move the monitor the overlay is
+    /// already sitting on, not just add/remove others.
+    fn refresh_monitors(&mut self) {
+        let mut monitors = query_monitors(&self.conn, self.root);
+        if monitors.is_empty() {
+            monitors.push((0, 0, self.screen_width, self.screen_height));
+        }
+        self.monitors = monitors;
+        let idx = self.active_monitor.min(self.monitors.len() - 1);
+        self.place_on_monitor(idx);
+    }
+
+    /// Set the window's *input region* via the XShape extension, independent
+    /// of its visible bounds: pointer/touch events land on whatever is
+    /// beneath the window outside these rectangles, instead of being
+    /// intercepted by it. An empty slice makes the whole window click-through.
+    pub fn set_input_region(&mut self, rects: &[(i16, i16, u16, u16)]) {
+        let rectangles: Vec<Rectangle> = rects.iter()
+            .map(|&(x, y, width, height)| Rectangle { x, y, width, height })
+            .collect();
+        let _ = self.conn.shape_rectangles(
+            shape::SO::SET,
+            shape::SK::INPUT,
+            ClipOrdering::UNSORTED,
+            self.window,
+            0,
+            0,
+            &rectangles,
+        );
+        let _ = self.conn.flush();
+    }
+
+    /// Convenience for the common cases: fully click-through (popup mode,
+    /// where the small always-on-top toast shouldn't steal clicks meant for
+    /// the game behind it) or an input region covering the whole window
+    /// (menu mode, where rows are actually interactive).
+    pub fn set_click_through(&mut self, enabled: bool) {
+        if enabled {
+            self.set_input_region(&[]);
+        } else {
+            self.set_input_region(&[(0, 0, self.current_width, self.current_height)]);
+        }
+    }
+}
+
+impl crate::surface::OverlaySurface for OverlayWindow {
+    fn present(&mut self, pixels: &[u32]) {
+        let (w, h) = (self.current_width, self.current_height);
+        self.update_pixels(pixels, w, h);
+    }
+
+    fn resize(&mut self, width: u16, height: u16) {
+        OverlayWindow::resize(self, width, height);
+    }
+
+    fn show(&mut self) {
+        OverlayWindow::show(self);
+    }
+
+    fn hide(&mut self) {
+        OverlayWindow::hide(self);
+    }
+
+    fn poll_events(&mut self) {
+        OverlayWindow::poll_events(self);
+    }
+
+    fn screen_size(&self) -> (u16, u16) {
+        OverlayWindow::screen_size(self)
+    }
+
+    fn supports_input_grab(&self) -> bool {
+        false
+    }
+
+    fn set_click_through(&mut self, enabled: bool) {
+        OverlayWindow::set_click_through(self, enabled);
+    }
 }
 
 fn find_argb_visual(screen: &Screen) -> Option<(Visualid, u8)> {
@@ -164,3 +373,74 @@ fn intern_atom(conn: &RustConnection, name: &str) -> Result<Atom, String> {
         .map(|r| r.atom)
         .map_err(|e| e.to_string())
 }
+
+/// Enumerate connected outputs' CRTC geometries via RandR. Empty if the
+/// server has no RandR, or on any reply error — callers fall back to the
+/// root screen's own dimensions.
+fn query_monitors(conn: &RustConnection, root: Window) -> Vec<(i16, i16, u16, u16)> {
+    let Ok(resources) = conn.randr_get_screen_resources_current(root).and_then(|c| c.reply()) else {
+        return Vec::new();
+    };
+
+    resources.outputs.iter().filter_map(|&output| {
+        let info = conn.randr_get_output_info(output, resources.config_timestamp).ok()?.reply().ok()?;
+        if info.connection != randr::Connection::CONNECTED || info.crtc == 0 {
+            return None;
+        }
+        let crtc = conn.randr_get_crtc_info(info.crtc, resources.config_timestamp).ok()?.reply().ok()?;
+        Some((crtc.x, crtc.y, crtc.width, crtc.height))
+    }).collect()
+}
+
+/// Diff two equal-length pixel buffers and return a minimal set of
+/// row-aligned bounding rectangles covering every changed pixel. Adjacent
+/// dirty rows are coalesced into a single rectangle spanning the union of
+/// their changed columns, trading a slightly looser bound for far fewer
+/// `put_image` calls than one rect per row.
+fn dirty_rects(prev: &[u32], curr: &[u32], width: u16, height: u16) -> Vec<(u16, u16, u16, u16)> {
+    let w = width as usize;
+
+    let row_span = |row: usize| -> Option<(usize, usize)> {
+        let start = row * w;
+        let (mut lo, mut hi) = (None, None);
+        for col in 0..w {
+            if prev[start + col] != curr[start + col] {
+                lo.get_or_insert(col);
+                hi = Some(col);
+            }
+        }
+        Some((lo?, hi?))
+    };
+
+    let mut rects = Vec::new();
+    let mut row = 0usize;
+    while row < height as usize {
+        let Some((mut lo, mut hi)) = row_span(row) else { row += 1; continue };
+        let start_row = row;
+        row += 1;
+        while row < height as usize {
+            let Some((row_lo, row_hi)) = row_span(row) else { break };
+            lo = lo.min(row_lo);
+            hi = hi.max(row_hi);
+            row += 1;
+        }
+        rects.push((lo as u16, start_row as u16, (hi - lo + 1) as u16, (row - start_row) as u16));
+    }
+    rects
+}
+
+/// Index into `monitors` of the RandR-reported primary output, or `0` if
+/// there is no primary (or no RandR at all).
+fn primary_monitor_index(conn: &RustConnection, root: Window, monitors: &[(i16, i16, u16, u16)]) -> usize {
+    let Ok(primary) = conn.randr_get_output_primary(root).and_then(|c| c.reply()) else { return 0 };
+    let Some(info) = conn.randr_get_output_info(primary.output, x11rb::CURRENT_TIME).ok()
+        .and_then(|c| c.reply().ok()) else { return 0 };
+    if info.crtc == 0 {
+        return 0;
+    }
+    let Some(crtc) = conn.randr_get_crtc_info(info.crtc, x11rb::CURRENT_TIME).ok()
+        .and_then(|c| c.reply().ok()) else { return 0 };
+
+    monitors.iter().position(|&(x, y, w, h)| (x, y, w, h) == (crtc.x, crtc.y, crtc.width, crtc.height))
+        .unwrap_or(0)
+}